@@ -0,0 +1,960 @@
+// vendored from batis4sqlx-macros 0.1.5, the upstream code keeps its own shape so it still diffs cleanly.
+#![allow(
+    clippy::collapsible_if,
+    clippy::derivable_impls,
+    clippy::let_and_return,
+    clippy::needless_else,
+    clippy::single_match
+)]
+
+use proc_macro::TokenStream;
+use proc_macro2::{Ident, Literal, Span};
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{
+    Attribute, Expr, Field, Fields, GenericArgument, Item, LitStr, PathArguments, Result, Token,
+    Type, parse_macro_input,
+};
+
+#[derive(Debug)]
+struct EntityAttr {
+    table_name: Option<String>,
+    id_generator: Option<String>,
+}
+
+impl Default for EntityAttr {
+    fn default() -> Self {
+        Self {
+            table_name: None,
+            id_generator: None,
+        }
+    }
+}
+
+impl Parse for EntityAttr {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut attr = EntityAttr::default();
+        let args: Punctuated<syn::MetaNameValue, Token![,]> = Punctuated::parse_terminated(input)?;
+        for meta in args {
+            match meta.path.get_ident().unwrap().to_string().as_str() {
+                "table_name" => {
+                    if let Expr::Lit(expr_lit) = &meta.value
+                        && let syn::Lit::Str(lit_str) = &expr_lit.lit
+                    {
+                        attr.table_name = Some(lit_str.value());
+                    }
+                }
+                "id_generator" => {
+                    if let Expr::Lit(expr_lit) = &meta.value
+                        && let syn::Lit::Str(lit_str) = &expr_lit.lit
+                    {
+                        attr.id_generator = Some(lit_str.value());
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(attr)
+    }
+}
+
+#[proc_macro_attribute]
+pub fn entity(attr: TokenStream, input: TokenStream) -> TokenStream {
+    let entity_attr = syn::parse_macro_input!(attr as EntityAttr);
+    let item = parse_macro_input!(input as Item);
+
+    let mut struct_item = match item.clone() {
+        Item::Struct(s) => s,
+        _ => {
+            return syn::Error::new(item.span(), "#[entity] only use struct")
+                .to_compile_error()
+                .into();
+        }
+    };
+
+    let struct_name = &struct_item.ident;
+    let table_name = if let Some(table_name) = &entity_attr.table_name {
+        table_name.as_str()
+    } else {
+        &pascal_to_snake(&struct_name.to_string())
+    };
+
+    let mut primary_keys = vec![];
+    let mut primary_key_fields = vec![];
+    let mut columns = vec![];
+    let mut values = vec![];
+    match &mut struct_item.fields {
+        Fields::Named(fields_named) => {
+            for field in &mut fields_named.named {
+                let attrs = &field.attrs;
+                let mut entity_field_attr_opt = None;
+                for attr in attrs {
+                    entity_field_attr_opt = EntityFieldAttr::from_attrs(attr).unwrap();
+                    // the first #[entity_field] takes effect, other attributes like #[sqlx] may come before it.
+                    if entity_field_attr_opt.is_some() {
+                        break;
+                    }
+                }
+                let entity_field_attr = entity_field_attr_opt.clone().unwrap_or_default();
+                let rust_name = field.ident.clone().unwrap().to_string();
+                let column_name = entity_field_attr
+                    .name
+                    .clone()
+                    .unwrap_or_else(|| rust_name.clone());
+                if entity_field_attr.primary_key {
+                    primary_keys.push(column_name.clone());
+                    primary_key_fields.push((field.ident.clone().unwrap(), field.ty.clone()));
+                }
+                columns.push(column_tokens(
+                    &rust_name,
+                    &column_name,
+                    &field.ty,
+                    &entity_field_attr,
+                ));
+                let field_ident = field.ident.clone().unwrap();
+                values.push(if entity_field_attr.skip {
+                    quote!(batis4sqlx::wrapper::SqlValue::Null)
+                } else {
+                    quote!(self.#field_ident.clone().into())
+                });
+                add_sqlx_attr(field, entity_field_attr_opt);
+            }
+        }
+        _ => {}
+    };
+
+    if primary_keys.is_empty() {
+        primary_keys.push("id".to_string());
+    }
+    let field_name_lit = Literal::string(primary_keys[0].as_str());
+
+    let generate_primary_key = match &entity_attr.id_generator {
+        Some(id_generator) => {
+            match generate_primary_key_tokens(id_generator, &primary_key_fields, struct_item.span())
+            {
+                Ok(tokens) => tokens,
+                Err(err) => return err.to_compile_error().into(),
+            }
+        }
+        None => quote! {},
+    };
+
+    let result = quote! {
+        #struct_item
+
+        impl batis4sqlx::Entity for #struct_name {
+            fn table_name() -> &'static str {
+                #table_name
+            }
+            fn primary_key<'b>() -> batis4sqlx::LambdaField<'b> {
+                batis4sqlx::LambdaField::new(#field_name_lit)
+            }
+            fn primary_keys<'b>() -> Vec<batis4sqlx::LambdaField<'b>> {
+                vec![#(batis4sqlx::LambdaField::new(#primary_keys)),*]
+            }
+            fn columns() -> &'static [batis4sqlx::Column] {
+                const COLUMNS: &[batis4sqlx::Column] = &[#(#columns),*];
+                COLUMNS
+            }
+            fn values(&self) -> Vec<batis4sqlx::wrapper::SqlValue> {
+                vec![#(#values),*]
+            }
+            #generate_primary_key
+        }
+    }
+    .into();
+    result
+}
+
+fn generate_primary_key_tokens(
+    id_generator: &str,
+    primary_key_fields: &[(Ident, Type)],
+    span: Span,
+) -> Result<proc_macro2::TokenStream> {
+    let [(field_ident, field_type)] = primary_key_fields else {
+        return Err(syn::Error::new(
+            span,
+            "id_generator needs exactly one #[entity_field(primary_key)] field",
+        ));
+    };
+    let Some(value_type) = option_inner_type(field_type) else {
+        return Err(syn::Error::new(
+            field_type.span(),
+            "id_generator needs an Option primary key",
+        ));
+    };
+    let generator = match id_generator {
+        "snowflake" => quote!(batis4sqlx::id::SNOWFLAKE),
+        "uuid_v4" => quote!(batis4sqlx::id::UuidV4),
+        "uuid_v7" => quote!(batis4sqlx::id::UuidV7),
+        path => {
+            let path: syn::Path = syn::parse_str(path)?;
+            quote!(#path)
+        }
+    };
+    Ok(quote! {
+        fn generate_primary_key(&mut self) {
+            if self.#field_ident.is_none() {
+                self.#field_ident = Some(
+                    <_ as batis4sqlx::id::IdGenerator<#value_type>>::next_id(&#generator)
+                );
+            }
+        }
+    })
+}
+
+fn column_tokens(
+    rust_name: &str,
+    column_name: &str,
+    ty: &Type,
+    entity_field_attr: &EntityFieldAttr,
+) -> proc_macro2::TokenStream {
+    let value_type = option_inner_type(ty);
+    let sql_type = entity_field_attr
+        .sql_type
+        .clone()
+        .unwrap_or_else(|| sql_type_hint(value_type.unwrap_or(ty)).to_string());
+    let nullable =
+        value_type.is_some() && !entity_field_attr.primary_key && !entity_field_attr.not_null;
+    let primary_key = entity_field_attr.primary_key;
+    let skip = entity_field_attr.skip;
+    let default = match &entity_field_attr.default {
+        Some(default) => quote!(Some(#default)),
+        None => quote!(None),
+    };
+    let update_strategy = match entity_field_attr.update_strategy.as_deref() {
+        Some("always") => quote!(Some(batis4sqlx::UpdateStrategy::Always)),
+        Some("not_null") => quote!(Some(batis4sqlx::UpdateStrategy::NotNull)),
+        Some("not_empty") => quote!(Some(batis4sqlx::UpdateStrategy::NotEmpty)),
+        Some("never") => quote!(Some(batis4sqlx::UpdateStrategy::Never)),
+        _ => quote!(None),
+    };
+    quote! {
+        batis4sqlx::Column {
+            field: #rust_name,
+            name: #column_name,
+            sql_type: #sql_type,
+            nullable: #nullable,
+            primary_key: #primary_key,
+            skip: #skip,
+            default: #default,
+            update_strategy: #update_strategy,
+        }
+    }
+}
+
+/// mysql column type of a rust type, empty when unknown.
+///
+/// types are matched by their whole path and generic argument, e.g. `Vec<u8>` but not `Vec<String>`,
+/// a name only known from its last segment like `Value` stays empty.
+fn sql_type_hint(ty: &Type) -> &'static str {
+    let Type::Path(tp) = ty else {
+        return "";
+    };
+    if tp.qself.is_some() {
+        return "";
+    }
+    let Some(seg) = tp.path.segments.last() else {
+        return "";
+    };
+    // `None` without generic arguments, empty for arguments other than a single type path.
+    let arg = match &seg.arguments {
+        PathArguments::None => None,
+        PathArguments::AngleBracketed(args) => match args.args.first() {
+            Some(GenericArgument::Type(Type::Path(arg))) if args.args.len() == 1 => {
+                Some(path_name(&arg.path))
+            }
+            _ => Some(String::new()),
+        },
+        PathArguments::Parenthesized(_) => return "",
+    };
+    match (path_name(&tp.path).as_str(), arg.as_deref()) {
+        ("bool", None) => "BOOLEAN",
+        ("i8", None) => "TINYINT",
+        ("i16", None) => "SMALLINT",
+        ("i32", None) => "INT",
+        ("i64", None) => "BIGINT",
+        ("u8", None) => "TINYINT UNSIGNED",
+        ("u16", None) => "SMALLINT UNSIGNED",
+        ("u32", None) => "INT UNSIGNED",
+        ("u64", None) => "BIGINT UNSIGNED",
+        ("f32", None) => "FLOAT",
+        ("f64", None) => "DOUBLE",
+        ("String" | "std::string::String", None) => "VARCHAR(255)",
+        ("Vec" | "std::vec::Vec", Some("u8")) => "BLOB",
+        ("Decimal" | "rust_decimal::Decimal" | "sqlx::types::Decimal", None) => "DECIMAL(65, 30)",
+        ("NaiveDate" | "chrono::NaiveDate" | "sqlx::types::chrono::NaiveDate", None) => "DATE",
+        ("NaiveTime" | "chrono::NaiveTime" | "sqlx::types::chrono::NaiveTime", None) => "TIME",
+        (
+            "NaiveDateTime" | "chrono::NaiveDateTime" | "sqlx::types::chrono::NaiveDateTime",
+            None,
+        ) => "DATETIME",
+        (
+            "DateTime" | "chrono::DateTime" | "sqlx::types::chrono::DateTime",
+            Some("Utc" | "chrono::Utc" | "sqlx::types::chrono::Utc"),
+        ) => "TIMESTAMP",
+        ("Uuid" | "uuid::Uuid" | "sqlx::types::Uuid", None) => "BINARY(16)",
+        ("Json" | "sqlx::types::Json", Some(_)) => "JSON",
+        ("JsonValue" | "sqlx::types::JsonValue" | "serde_json::Value", None) => "JSON",
+        _ => "",
+    }
+}
+
+/// `a::b::C` of a path, without generic arguments.
+fn path_name(path: &syn::Path) -> String {
+    path.segments
+        .iter()
+        .map(|seg| seg.ident.to_string())
+        .collect::<Vec<_>>()
+        .join("::")
+}
+
+#[derive(Debug, Default, Clone)]
+struct EntityFieldAttr {
+    pub primary_key: bool,
+    pub skip: bool,
+    pub name: Option<String>,
+    pub sql_type: Option<String>,
+    pub not_null: bool,
+    pub default: Option<String>,
+    pub update_strategy: Option<String>,
+}
+
+impl EntityFieldAttr {
+    pub fn from_attrs(attr: &Attribute) -> Result<Option<Self>> {
+        if attr.path().is_ident("entity_field") {
+            return Ok(Some(attr.parse_args_with(EntityFieldAttr::parse)?));
+        }
+        Ok(None)
+    }
+}
+
+impl Parse for EntityFieldAttr {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut result = EntityFieldAttr::default();
+
+        while !input.is_empty() {
+            let ident: Ident = input.parse()?;
+
+            if ident == "primary_key" {
+                result.primary_key = true;
+            } else if ident == "skip" {
+                result.skip = true;
+            } else if ident == "name" {
+                input.parse::<Token![=]>()?;
+                let lit: LitStr = input.parse()?;
+                result.name = Some(lit.value());
+            } else if ident == "sql_type" {
+                input.parse::<Token![=]>()?;
+                let lit: LitStr = input.parse()?;
+                result.sql_type = Some(lit.value());
+            } else if ident == "not_null" {
+                result.not_null = true;
+            } else if ident == "default" {
+                input.parse::<Token![=]>()?;
+                let lit: LitStr = input.parse()?;
+                result.default = Some(lit.value());
+            } else if ident == "update_strategy" {
+                input.parse::<Token![=]>()?;
+                let lit: LitStr = input.parse()?;
+                if !matches!(
+                    lit.value().as_str(),
+                    "always" | "not_null" | "not_empty" | "never"
+                ) {
+                    return Err(syn::Error::new(
+                        lit.span(),
+                        "update_strategy must be \"always\", \"not_null\", \"not_empty\" or \"never\"",
+                    ));
+                }
+                result.update_strategy = Some(lit.value());
+            } else {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    format!("未知的 entity_field 属性: {}", ident),
+                ));
+            }
+
+            // 处理逗号
+            if input.peek(Token![,]) {
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+#[proc_macro_derive(Entity, attributes(entity_field))]
+pub fn entity_field(input: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(input as Item);
+
+    let struct_item = match &item {
+        Item::Struct(s) => s,
+        _ => {
+            return syn::Error::new(item.span(), "#[derive[Entity]] only use struct")
+                .to_compile_error()
+                .into();
+        }
+    };
+
+    let struct_name = &struct_item.ident;
+    let field_atts = match &struct_item.fields {
+        Fields::Named(fields_named) => fields_named
+            .named
+            .iter()
+            .map(|f| {
+                // 字段名称
+                let field_name = f.ident.clone().unwrap();
+                // 字段类型
+                let field_type = &f.ty;
+                // 字段上的宏
+                let atts = &f.attrs;
+                (field_name, field_type, atts)
+            })
+            .collect(),
+        _ => vec![],
+    };
+    let mut field_keys = vec![];
+    let mut get_field_value = vec![];
+    let mut lambda_fields = vec![];
+    let mut set_primary_key = vec![];
+    let mut primary_key_count = 0;
+    'outside: for (field, field_type, attrs) in field_atts {
+        let field_name = field.to_string();
+        let field_ident = Ident::new(&field_name, Span::call_site());
+        let func_ident = Ident::new(&format!("{field_name}_field"), Span::call_site());
+        let mut field_name_alis_lit = Literal::string(field.to_string().as_str());
+        for attr in attrs {
+            if let Some(entity_field_attr) = EntityFieldAttr::from_attrs(attr).unwrap() {
+                if entity_field_attr.skip {
+                    continue 'outside;
+                }
+                if let Some(name) = entity_field_attr.name {
+                    field_name_alis_lit = Literal::string(&name);
+                }
+                if entity_field_attr.primary_key {
+                    primary_key_count += 1;
+                }
+                if entity_field_attr.primary_key {
+                    if let Some(result) = parse_u64_or_option_u64(field_type) {
+                        if result {
+                            set_primary_key.push(quote! { self.#field_ident = Some(id) });
+                        } else {
+                            set_primary_key.push(quote! { self.#field_ident = id });
+                        }
+                    }
+                }
+            }
+        }
+        let value_type = option_inner_type(field_type).unwrap_or(field_type);
+        lambda_fields.push(quote! {
+            pub fn #func_ident() -> batis4sqlx::Field<Self, #value_type> {
+                batis4sqlx::Field::new(#field_name_alis_lit)
+            }
+        });
+        field_keys.push(quote! {
+            field_keys.push(batis4sqlx::LambdaField::new(#field_name_alis_lit));
+        });
+        get_field_value.push(quote! {
+            k if k == #field_name_alis_lit => {
+                if let Some(ref value) = self.#field_ident {
+                    value.clone().into()
+                } else {
+                    batis4sqlx::wrapper::SqlValue::Null
+                }
+            },
+        })
+    }
+    // last insert id only belongs to a single column primary key.
+    if primary_key_count > 1 {
+        set_primary_key.clear();
+    }
+    lambda_fields.push(quote! {
+        pub fn field_keys() -> Vec<batis4sqlx::LambdaField<'b>> {
+            let mut field_keys = vec![];
+            #(#field_keys)*
+            field_keys
+        }
+
+        pub fn get_field_value(&self, key: &batis4sqlx::LambdaField<'b>) -> batis4sqlx::wrapper::SqlValue {
+            match **key {
+                #(#get_field_value)*
+                &_ => {
+                    panic!("unknown field")
+                }
+            }
+        }
+    });
+    let result = quote! {
+        impl<'b> #struct_name {
+            #(#lambda_fields)*
+        }
+
+        impl #struct_name {
+            pub fn set_primary_key(&mut self, id: u64) {
+                #(#set_primary_key)*
+            }
+        }
+    }
+    .into();
+    result
+}
+
+#[derive(Debug, Default)]
+struct RepositoryAttr {
+    db_type: String,
+    entity_path: String,
+}
+
+impl Parse for RepositoryAttr {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut attr = RepositoryAttr::default();
+        let args: Punctuated<syn::MetaNameValue, Token![,]> = Punctuated::parse_terminated(input)?;
+        for meta in args {
+            match meta.path.get_ident().unwrap().to_string().as_str() {
+                "db_type" => {
+                    if let Expr::Lit(expr_lit) = &meta.value
+                        && let syn::Lit::Str(lit_str) = &expr_lit.lit
+                    {
+                        attr.db_type = lit_str.value();
+                    }
+                }
+                "entity_path" => {
+                    if let Expr::Lit(expr_lit) = &meta.value
+                        && let syn::Lit::Str(lit_str) = &expr_lit.lit
+                    {
+                        attr.entity_path = lit_str.value();
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(attr)
+    }
+}
+
+#[proc_macro_attribute]
+pub fn repository(attr: TokenStream, input: TokenStream) -> TokenStream {
+    let repository_attr = syn::parse_macro_input!(attr as RepositoryAttr);
+    let item = parse_macro_input!(input as Item);
+
+    let struct_item = match &item {
+        Item::Struct(s) => s,
+        _ => {
+            return syn::Error::new(item.span(), "#[repository] only use struct")
+                .to_compile_error()
+                .into();
+        }
+    };
+
+    let struct_name = &struct_item.ident;
+    let entity_path = repository_attr.entity_path.as_str();
+    let entity_path_ident = Ident::new(entity_path, Span::call_site());
+    let impls = match repository_attr.db_type.to_lowercase().as_str() {
+        "mysql" => {
+            quote! {
+                pub async fn save(&self, vo: &mut #entity_path_ident) -> batis4sqlx::Result<u64> {
+                    self.save_db_opt(vo, None).await
+                }
+
+                pub async fn save_db(&self, vo: &mut #entity_path_ident, db: &mut sqlx::Transaction<'_, sqlx::MySql>) -> batis4sqlx::Result<u64> {
+                    self.save_db_opt(vo, Some(db)).await
+                }
+
+                pub async fn save_db_opt(&self, vo: &mut #entity_path_ident, db: Option<&mut sqlx::Transaction<'_, sqlx::MySql>>) -> batis4sqlx::Result<u64> {
+                    <#entity_path_ident as batis4sqlx::Entity>::generate_primary_key(vo);
+                    let mut insert_sql = format!("INSERT INTO {} (", <#entity_path_ident as batis4sqlx::Entity>::table_name());
+                    let mut fields = vec![];
+                    let mut values = vec![];
+                    for field in #entity_path_ident::field_keys() {
+                        let sql_value = vo.get_field_value(&field);
+                        match sql_value {
+                            batis4sqlx::wrapper::SqlValue::Null => {
+                                continue;
+                            }
+                            _ => {
+                                fields.push(field);
+                                values.push(sql_value);
+                            }
+                        }
+                    }
+                    let fields = fields
+                        .iter()
+                        .map(|field| (**field).to_string())
+                        .collect::<Vec<String>>()
+                        .join(",");
+                    insert_sql.push_str(&fields);
+                    insert_sql.push_str(") VALUES (");
+                    {
+                        let values = values
+                            .iter()
+                            .map(|_| "?".to_string())
+                            .collect::<Vec<String>>()
+                            .join(",");
+                        insert_sql.push_str(&values);
+                    }
+                    insert_sql.push_str(")");
+                    let executer = sqlx::query_with(&insert_sql, batis4sqlx::repository::arguments(&values)?);
+                    let result = if let Some(db) = db {
+                        executer.execute(&mut **db).await?
+                    } else {
+                        executer.execute(self.borrow_db()).await?
+                    };
+                    // 0 when the primary key was not generated by AUTO_INCREMENT
+                    if result.last_insert_id() != 0 {
+                        vo.set_primary_key(result.last_insert_id());
+                    }
+                    Ok(result.rows_affected())
+                }
+
+
+                pub async fn save_batch(&self, vec: &mut Vec<#entity_path_ident>) -> batis4sqlx::Result<u64> {
+                    self.save_batch_db_opt(vec, None).await
+                }
+
+                pub async fn save_batch_db(&self, vec: &mut Vec<#entity_path_ident>, db: &mut sqlx::Transaction<'_, sqlx::MySql>) -> batis4sqlx::Result<u64> {
+                    self.save_batch_db_opt(vec, Some(db)).await
+                }
+
+                pub async fn save_batch_db_opt(&self, vec: &mut Vec<#entity_path_ident>, db: Option<&mut sqlx::Transaction<'_, sqlx::MySql>>) -> batis4sqlx::Result<u64> {
+                    for vo in vec.iter_mut() {
+                        <#entity_path_ident as batis4sqlx::Entity>::generate_primary_key(vo);
+                    }
+                    let mut insert_sql = format!(
+                        "INSERT INTO {} (",
+                        <#entity_path_ident as batis4sqlx::Entity>::table_name()
+                    );
+                    let lambda_fields = #entity_path_ident::field_keys();
+                    let mut values = vec![];
+                    for vo in vec.iter() {
+                        let mut sql_values = vec![];
+                        for field in &lambda_fields {
+                            let sql_value = vo.get_field_value(field);
+                            match sql_value {
+                                batis4sqlx::wrapper::SqlValue::Null => {
+                                    sql_values.push(batis4sqlx::wrapper::SqlValue::Null);
+                                }
+                                _ => {
+                                    sql_values.push(sql_value);
+                                }
+                            }
+                        }
+                        values.push(sql_values);
+                    }
+                    let fields = lambda_fields
+                        .iter()
+                        .map(|field| (**field).to_string())
+                        .collect::<Vec<String>>()
+                        .join(",");
+                    insert_sql.push_str(&fields);
+                    insert_sql.push_str(") VALUES ");
+                    let mut sql_values_bind = vec![];
+                    for i in 0..vec.len() {
+                        let sql_values = &values[i];
+                        insert_sql.push_str("(");
+                        let sql_values = sql_values
+                        .iter()
+                        .map(|sql_value| {
+                            sql_values_bind.push(sql_value.clone());
+                            "?".to_string()
+                        })
+                        .collect::<Vec<String>>()
+                        .join(",");
+                        insert_sql.push_str(&sql_values);
+                        insert_sql.push_str("),");
+                    }
+                    insert_sql.pop();
+                    let executer = sqlx::query_with(&insert_sql, batis4sqlx::repository::arguments(&sql_values_bind)?);
+                    let result = if let Some(db) = db {
+                        executer.execute(&mut **db).await
+                    } else {
+                        executer.execute(self.borrow_db()).await
+                    };
+                    if let Ok(result) = result {
+                        Ok(result.rows_affected())
+                    } else {
+                        Err(result.err().unwrap())
+                    }
+                }
+
+                /// sets the fields chosen by their update strategy, `None` fields keep the column value by default.
+                pub async fn update_by_primary_key(&self, vo: &#entity_path_ident) -> batis4sqlx::Result<u64> {
+                    self.update_by_primary_key_db_opt(vo, None).await
+                }
+
+                pub async fn update_by_primary_key_db(&self, vo: &#entity_path_ident, db: &mut sqlx::Transaction<'_, sqlx::MySql>) -> batis4sqlx::Result<u64> {
+                    self.update_by_primary_key_db_opt(vo, Some(db)).await
+                }
+
+                pub async fn update_by_primary_key_db_opt(&self, vo: &#entity_path_ident, db: Option<&mut sqlx::Transaction<'_, sqlx::MySql>>) -> batis4sqlx::Result<u64> {
+                    let (update_sql, values) = batis4sqlx::repository::update_by_primary_key_sql(vo)?;
+                    let executer = sqlx::query_with(&update_sql, batis4sqlx::repository::arguments(&values)?);
+                    let result = if let Some(db) = db {
+                        executer.execute(&mut **db).await?
+                    } else {
+                        executer.execute(self.borrow_db()).await?
+                    };
+                    Ok(result.rows_affected())
+                }
+
+                /// updates every entity by its primary key with `CASE` statements of `chunk_size` entities,
+                /// all in one transaction, `None` fields keep the column value. returns the rows affected per chunk.
+                pub async fn update_batch_by_primary_key(&self, vec: &[#entity_path_ident], chunk_size: usize) -> batis4sqlx::Result<Vec<u64>> {
+                    self.update_batch_by_primary_key_db_opt(vec, chunk_size, None).await
+                }
+
+                pub async fn update_batch_by_primary_key_db(&self, vec: &[#entity_path_ident], chunk_size: usize, db: &mut sqlx::Transaction<'_, sqlx::MySql>) -> batis4sqlx::Result<Vec<u64>> {
+                    self.update_batch_by_primary_key_db_opt(vec, chunk_size, Some(db)).await
+                }
+
+                pub async fn update_batch_by_primary_key_db_opt(&self, vec: &[#entity_path_ident], chunk_size: usize, db: Option<&mut sqlx::Transaction<'_, sqlx::MySql>>) -> batis4sqlx::Result<Vec<u64>> {
+                    if let Some(db) = db {
+                        return batis4sqlx::repository::update_batch_by_primary_key(&mut **db, vec, chunk_size).await;
+                    }
+                    let mut tx = self.borrow_db().begin().await?;
+                    let rows = batis4sqlx::repository::update_batch_by_primary_key(&mut *tx, vec, chunk_size).await?;
+                    tx.commit().await?;
+                    Ok(rows)
+                }
+
+                /// composite primary keys take tuples, e.g. `HashSet<(u64, u64)>`.
+                pub async fn delete_in_primary_keys<K>(&self, primary_keys: std::collections::HashSet<K>) -> batis4sqlx::Result<u64>
+                where
+                    K: batis4sqlx::wrapper::SqlTuple,
+                {
+                    self.delete_in_primary_keys_db_opt(primary_keys, None).await
+                }
+
+                pub async fn delete_in_primary_keys_db<K>(&self, primary_keys: std::collections::HashSet<K>, db: &mut sqlx::Transaction<'_, sqlx::MySql>) -> batis4sqlx::Result<u64>
+                where
+                    K: batis4sqlx::wrapper::SqlTuple,
+                {
+                    self.delete_in_primary_keys_db_opt(primary_keys, Some(db)).await
+                }
+
+                /// several statements run in one transaction.
+                pub async fn delete_in_primary_keys_db_opt<K>(&self, primary_keys: std::collections::HashSet<K>, db: Option<&mut sqlx::Transaction<'_, sqlx::MySql>>) -> batis4sqlx::Result<u64>
+                where
+                    K: batis4sqlx::wrapper::SqlTuple,
+                {
+                    let columns = <#entity_path_ident as batis4sqlx::Entity>::primary_keys();
+                    let mut keys = vec![];
+                    for primary_key in primary_keys {
+                        let values = primary_key.into_values();
+                        batis4sqlx::repository::check_primary_key_arity(columns.len(), values.len())?;
+                        keys.push(values);
+                    }
+                    if keys.is_empty() {
+                        return Ok(0);
+                    }
+                    if let Some(db) = db {
+                        return batis4sqlx::repository::delete_in_primary_keys::<#entity_path_ident>(&mut **db, &keys).await;
+                    }
+                    let mut tx = self.borrow_db().begin().await?;
+                    let rows = batis4sqlx::repository::delete_in_primary_keys::<#entity_path_ident>(&mut *tx, &keys).await?;
+                    tx.commit().await?;
+                    Ok(rows)
+                }
+            }
+        }
+        _ => {
+            return syn::Error::new(item.span(), "unknow db type.")
+                .to_compile_error()
+                .into();
+        }
+    };
+
+    let expanded = quote! {
+        #item
+
+        impl #struct_name {
+            #impls
+        }
+    }
+    .into();
+    expanded
+}
+
+fn pascal_to_snake(name: &str) -> String {
+    let mut result = String::with_capacity(name.len());
+    let mut prev_is_upper = false;
+    let mut prev_is_alpha = false;
+
+    for (i, ch) in name.chars().enumerate() {
+        if ch.is_uppercase() {
+            if i > 0 && (!prev_is_upper || prev_is_alpha) {
+                result.push('_');
+            }
+            result.push(ch.to_ascii_lowercase());
+            prev_is_upper = true;
+            prev_is_alpha = true;
+        } else {
+            result.push(ch);
+            prev_is_upper = false;
+            prev_is_alpha = ch.is_alphabetic();
+        }
+    }
+
+    result
+}
+
+fn parse_u64_or_option_u64(ty: &Type) -> Option<bool> {
+    if is_u64(ty) {
+        return Some(false);
+    }
+
+    if let Some(inner_ty) = option_inner_type(ty)
+        && is_u64(inner_ty)
+    {
+        return Some(true);
+    }
+
+    None
+}
+
+/// `T` of `Option<T>`, `None` for other types.
+fn option_inner_type(ty: &Type) -> Option<&Type> {
+    if let Type::Path(tp) = ty
+        && tp.qself.is_none()
+        && let Some(seg) = tp.path.segments.last()
+        && seg.ident == "Option"
+        && let PathArguments::AngleBracketed(args) = &seg.arguments
+        && args.args.len() == 1
+        && let Some(GenericArgument::Type(inner_ty)) = args.args.first()
+    {
+        return Some(inner_ty);
+    }
+
+    None
+}
+
+fn is_u64(ty: &Type) -> bool {
+    matches!(
+        ty,
+        Type::Path(tp)
+            if tp.qself.is_none()
+            && tp.path.is_ident("u64")
+    )
+}
+
+#[derive(Debug, Default)]
+struct SqlxAttr {
+    default: bool,
+    skip: bool,
+    rename: Option<String>,
+}
+
+impl SqlxAttr {
+    pub fn from_attrs(attr: &Attribute) -> Result<Option<Self>> {
+        if attr.path().is_ident("sqlx") {
+            return Ok(Some(attr.parse_args_with(SqlxAttr::parse)?));
+        }
+        Ok(None)
+    }
+}
+
+impl Parse for SqlxAttr {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut result = SqlxAttr::default();
+
+        while !input.is_empty() {
+            let ident: Ident = input.parse()?;
+
+            if ident == "default" {
+                result.default = true;
+            } else if ident == "skip" {
+                result.skip = true;
+            } else if ident == "rename" {
+                input.parse::<Token![=]>()?;
+                let lit: LitStr = input.parse()?;
+                result.rename = Some(lit.value());
+            } else {
+            }
+
+            // 处理逗号
+            if input.peek(Token![,]) {
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+fn add_sqlx_attr(field: &mut Field, entity_field_attr_opt: Option<EntityFieldAttr>) {
+    let mut sqlx_skip = false;
+    let mut sqlx_default = false;
+    let mut sqlx_rename = None;
+    for attr in &field.attrs {
+        if let Some(sqlx_attr) = SqlxAttr::from_attrs(attr).unwrap() {
+            sqlx_skip = sqlx_attr.skip;
+            sqlx_default = sqlx_attr.default;
+            sqlx_rename = sqlx_attr.rename;
+        }
+    }
+    let mut meta_tokens = vec![];
+    if let Some(entity_field_attr) = entity_field_attr_opt {
+        if entity_field_attr.skip {
+            if sqlx_skip {
+                return;
+            } else {
+                meta_tokens.push(quote!(skip));
+            }
+        } else {
+            if !sqlx_default {
+                meta_tokens.push(quote!(default));
+            }
+            if sqlx_rename.is_none()
+                && let Some(ref name) = entity_field_attr.name
+            {
+                meta_tokens.push(quote!(rename = #name));
+            }
+        }
+    } else {
+        if !sqlx_skip && !sqlx_default {
+            meta_tokens.push(quote!(default));
+        }
+    }
+
+    if meta_tokens.is_empty() {
+        return;
+    }
+
+    let sqlx_attr = syn::parse_quote! {
+        #[sqlx( #(#meta_tokens),* )]
+    };
+
+    field.attrs.insert(0, sqlx_attr);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hint(ty: &str) -> &'static str {
+        sql_type_hint(&syn::parse_str::<Type>(ty).unwrap())
+    }
+
+    #[test]
+    fn sql_type_hint_matches_whole_types() {
+        assert_eq!(hint("i32"), "INT");
+        assert_eq!(hint("std::string::String"), "VARCHAR(255)");
+        assert_eq!(hint("Vec<u8>"), "BLOB");
+        assert_eq!(hint("DateTime<Utc>"), "TIMESTAMP");
+        assert_eq!(hint("chrono::NaiveDateTime"), "DATETIME");
+        assert_eq!(hint("Json<Settings>"), "JSON");
+        assert_eq!(hint("serde_json::Value"), "JSON");
+    }
+
+    #[test]
+    fn sql_type_hint_is_empty_when_unknown() {
+        assert_eq!(hint("Value"), "");
+        assert_eq!(hint("toml::Value"), "");
+        assert_eq!(hint("Vec<String>"), "");
+        assert_eq!(hint("DateTime<Local>"), "");
+        assert_eq!(hint("my::DateTime"), "");
+        assert_eq!(hint("i32<u8>"), "");
+    }
+}
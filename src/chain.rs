@@ -1,31 +1,175 @@
 use crate::Result;
-use crate::repository::bind_query;
-use crate::wrapper::{Bracket, GroupHaving, Order, SqlValue, Where, Wrapper};
-use crate::{Entity, LambdaField};
-use sqlx::mysql::MySqlRow;
-use sqlx::{FromRow, MySql, MySqlPool};
-use std::collections::HashSet;
+use crate::expr::{Expr, MatchMode, OrderBy, Window};
+use crate::repository::{Statement, arguments};
+use crate::wrapper::{
+    Bracket, Clause, GroupHaving, Hints, IndexHint, Lock, LockMode, LockWait, Order, SqlValue,
+    Where, Wrapper, check_invalid, comment_sql, invalid_where, split_in,
+};
+use crate::{Entity, FieldValue, IntoField, IntoFields, UpdateStrategy};
+use serde::Serialize;
+use sqlx::mysql::{MySqlArguments, MySqlConnection, MySqlQueryResult, MySqlRow};
+use sqlx::{Decode, Error, FromRow, MySql, MySqlPool, Row, Transaction, Type};
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
 use std::marker::PhantomData;
+use std::time::Duration;
+
+/// where a wrapper runs its statement, the pool or the connection of a transaction.
+enum Executor<'d> {
+    Pool(&'d MySqlPool),
+    Transaction(&'d mut MySqlConnection),
+}
+
+/// `$query.$method(executor).await` on the executor of a wrapper.
+macro_rules! execute {
+    ($db:expr, $query:expr, $method:ident) => {{
+        let query = $query;
+        match &mut $db {
+            Executor::Pool(pool) => query.$method(*pool).await,
+            Executor::Transaction(conn) => query.$method(&mut **conn).await,
+        }
+    }};
+}
+
+/// runs `statements` in one transaction, the one of the wrapper or a new one on the pool, returns the
+/// rows affected.
+async fn execute_statements(db: &mut Executor<'_>, statements: Vec<Statement>) -> Result<u64> {
+    match db {
+        Executor::Pool(pool) => {
+            let mut tx = pool.begin().await?;
+            let rows = execute_all(&mut tx, statements).await?;
+            tx.commit().await?;
+            Ok(rows)
+        }
+        Executor::Transaction(conn) => execute_all(conn, statements).await,
+    }
+}
+
+async fn execute_all(conn: &mut MySqlConnection, statements: Vec<Statement>) -> Result<u64> {
+    let mut rows = 0;
+    for (sql, values) in statements {
+        rows += sqlx::query_with(&sql, arguments(&values)?)
+            .execute(&mut *conn)
+            .await?
+            .rows_affected();
+    }
+    Ok(rows)
+}
+
+/// `ORDER BY .. LIMIT ? OFFSET ?`
+fn order_limit_sql(order: &[Order], limit: Option<u64>, offset: Option<u64>) -> String {
+    let mut sql = String::new();
+    if !order.is_empty() {
+        sql += " ORDER BY ";
+        let orders: Vec<String> = order
+            .iter()
+            .map(|o| {
+                if o.asc_desc {
+                    format!("{} ASC", o.field)
+                } else {
+                    format!("{} DESC", o.field)
+                }
+            })
+            .collect();
+
+        sql += &orders.join(", ");
+    }
+    if limit.is_some() {
+        sql += " LIMIT ?";
+    } else if offset.is_some() {
+        // MySQL has no OFFSET without LIMIT
+        sql += " LIMIT 18446744073709551615";
+    }
+    if offset.is_some() {
+        sql += " OFFSET ?";
+    }
+    sql
+}
+
+/// output column name of a select item, `u.id` is `id` and `COUNT(*) AS total` is `total`.
+fn output_name(field: &str) -> &str {
+    let field = field.trim();
+    if let Some(alias) = alias(field) {
+        return alias;
+    }
+    if field.contains('(') {
+        return field;
+    }
+    field.rsplit('.').next().unwrap_or(field).trim_matches('`')
+}
+
+/// trailing `AS name` of a select item, the `AS` of `CAST(x AS CHAR)` isn't followed by a bare name.
+fn alias(field: &str) -> Option<&str> {
+    let index = field.to_ascii_uppercase().rfind(" AS ")?;
+    let alias = field[index + 4..].trim();
+    if let Some(quoted) = alias
+        .strip_prefix('`')
+        .and_then(|alias| alias.strip_suffix('`'))
+    {
+        return (!quoted.is_empty() && !quoted.contains('`')).then_some(quoted);
+    }
+    let bare = !alias.is_empty()
+        && alias
+            .chars()
+            .all(|char| char.is_alphanumeric() || char == '_' || char == '$');
+    bare.then_some(alias)
+}
+
+fn limit_values(limit: Option<u64>, offset: Option<u64>) -> Vec<SqlValue> {
+    limit.into_iter().chain(offset).map(Into::into).collect()
+}
+
+/// one page of `QueryWrapper::page` and `UnionQuery::page`, `current` starts from 1.
+#[derive(Debug, Clone, Serialize)]
+pub struct Page<T> {
+    pub records: Vec<T>,
+    pub total: u64,
+    pub current: u64,
+    pub size: u64,
+}
+
+impl<T> Page<T> {
+    pub fn pages(&self) -> u64 {
+        if self.size == 0 {
+            return 0;
+        }
+        self.total.div_ceil(self.size)
+    }
+}
 
 pub struct QueryWrapper<'a, 'd, E>
 where
     E: Entity + for<'r> FromRow<'r, MySqlRow> + Send + Unpin,
 {
-    field: Vec<&'a str>,
+    field: Vec<Expr>,
     wheres: Vec<Where<'a>>,
     or_index: HashSet<usize>,
     bracket: Bracket,
     group_having: GroupHaving<'a>,
     order: Vec<Order<'a>>,
-    first: Option<&'a str>,
-    last: Option<&'a str>,
+    limit: Option<u64>,
+    offset: Option<u64>,
+    lock: Option<Lock>,
+    ctes: Vec<Cte<'a>>,
+    recursive: bool,
+    from: Option<Cow<'a, str>>,
+    hints: Hints<'a>,
+    first: Option<Clause<'a>>,
+    last: Option<Clause<'a>>,
     comment: Option<&'a str>,
-    db: &'d MySqlPool,
+    /// error of an embedded query, reported when the statement runs.
+    invalid: Option<String>,
+    db: Executor<'d>,
     _ignore: PhantomData<E>,
 }
 
 impl<'a, 'd, E: Entity + for<'r> FromRow<'r, MySqlRow> + Send + Unpin> QueryWrapper<'a, 'd, E> {
     pub fn new(db: &'d MySqlPool) -> Self {
+        Self::with_executor(Executor::Pool(db))
+    }
+
+    fn with_executor(db: Executor<'d>) -> Self {
         Self {
             field: vec![],
             wheres: vec![],
@@ -33,9 +177,17 @@ impl<'a, 'd, E: Entity + for<'r> FromRow<'r, MySqlRow> + Send + Unpin> QueryWrap
             bracket: Bracket::new(),
             group_having: GroupHaving::new(),
             order: vec![],
+            limit: None,
+            offset: None,
+            lock: None,
+            ctes: vec![],
+            recursive: false,
+            from: None,
+            hints: Hints::default(),
             first: None,
             last: None,
             comment: None,
+            invalid: None,
             db,
             _ignore: Default::default(),
         }
@@ -43,15 +195,15 @@ impl<'a, 'd, E: Entity + for<'r> FromRow<'r, MySqlRow> + Send + Unpin> QueryWrap
 
     pub fn group_by<F>(mut self, field_func: F) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
+        F: IntoField<'a, E>,
     {
-        self.group_having.field_push(*field_func());
+        self.group_having.field_push(field_func.into_field());
         self
     }
 
     pub fn group_by_flag<F>(mut self, field_func: F, flag: bool) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
+        F: IntoField<'a, E>,
     {
         if flag {
             self = self.group_by(field_func);
@@ -61,17 +213,17 @@ impl<'a, 'd, E: Entity + for<'r> FromRow<'r, MySqlRow> + Send + Unpin> QueryWrap
 
     pub fn group_by_vec<F>(mut self, field_func_vec: Vec<F>) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
+        F: IntoField<'a, E>,
     {
         for field_func in field_func_vec {
-            self.group_having.field_push(*field_func());
+            self.group_having.field_push(field_func.into_field());
         }
         self
     }
 
     pub fn group_by_vec_flag<F>(mut self, field_func_vec: Vec<F>, flag: bool) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
+        F: IntoField<'a, E>,
     {
         if flag {
             self = self.group_by_vec(field_func_vec);
@@ -140,28 +292,136 @@ impl<'a, 'd, E: Entity + for<'r> FromRow<'r, MySqlRow> + Send + Unpin> QueryWrap
 
     pub fn order_asc<F>(self, field_func: F) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
+        F: IntoField<'a, E>,
     {
-        self.order_asc_field(*field_func())
+        self.order_asc_field(field_func.into_field())
     }
 
     pub fn order_desc<F>(self, field_func: F) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
+        F: IntoField<'a, E>,
     {
-        self.order_desc_field(*field_func())
+        self.order_desc_field(field_func.into_field())
     }
 
     pub fn select<F>(mut self, field_func_vec: Vec<F>) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
+        F: IntoField<'a, E>,
     {
         for f in field_func_vec {
-            self.field.push(*f());
+            self.field.push(Expr::raw(f.into_field()));
         }
         self
     }
 
+    /// all entity columns except `field_func_vec`.
+    pub fn select_exclude<F>(self, field_func_vec: Vec<F>) -> Self
+    where
+        F: IntoField<'a, E>,
+    {
+        let exclude = field_func_vec
+            .into_iter()
+            .map(|f| f.into_field())
+            .collect::<Vec<_>>();
+        self.select_exclude_field(&exclude)
+    }
+
+    /// `field AS alias`, e.g. to decode into a DTO with `vec_as`.
+    pub fn select_as<F>(self, field_func: F, alias: &'a str) -> Self
+    where
+        F: IntoField<'a, E>,
+    {
+        self.select_as_field(field_func.into_field(), alias)
+    }
+
+    /// `LIMIT ?`, rendered after ORDER BY.
+    pub fn limit(mut self, limit: u64) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// `OFFSET ?`, without `limit` all remaining rows are returned.
+    pub fn offset(mut self, offset: u64) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// run on the connection of `tx` instead of the pool.
+    pub fn transaction(mut self, tx: &'d mut Transaction<'_, MySql>) -> Self {
+        self.db = Executor::Transaction(tx);
+        self
+    }
+
+    /// `USE INDEX (a, b)` after the table name, skipped when `from` is a CTE.
+    pub fn use_index(mut self, indexes: &'a [&'a str]) -> Self {
+        self.hints.push_index(IndexHint::Use, indexes);
+        self
+    }
+
+    /// `FORCE INDEX (a, b)` after the table name, skipped when `from` is a CTE.
+    pub fn force_index(mut self, indexes: &'a [&'a str]) -> Self {
+        self.hints.push_index(IndexHint::Force, indexes);
+        self
+    }
+
+    /// `IGNORE INDEX (a, b)` after the table name, skipped when `from` is a CTE.
+    pub fn ignore_index(mut self, indexes: &'a [&'a str]) -> Self {
+        self.hints.push_index(IndexHint::Ignore, indexes);
+        self
+    }
+
+    /// MySQL 8 optimizer hint in `/*+ .. */` after the statement keyword, e.g. `BKA(user)`.
+    pub fn hint(mut self, hint: &'a str) -> Self {
+        self.hints.push_optimizer(hint);
+        self
+    }
+
+    /// `MAX_EXECUTION_TIME(ms)` hint, MySQL stops the SELECT when it runs longer than `timeout`.
+    pub fn max_execution_time(mut self, timeout: Duration) -> Self {
+        self.hints
+            .push_optimizer(format!("MAX_EXECUTION_TIME({})", timeout.as_millis()));
+        self
+    }
+
+    /// `FOR UPDATE`, needs `transaction`.
+    pub fn for_update(mut self) -> Self {
+        self.lock = Some(Lock::new(LockMode::Update, self.lock_wait()));
+        self
+    }
+
+    /// `FOR SHARE` (MySQL 8), needs `transaction`.
+    pub fn for_share(mut self) -> Self {
+        self.lock = Some(Lock::new(LockMode::Share, self.lock_wait()));
+        self
+    }
+
+    /// `LOCK IN SHARE MODE` (MySQL 5.7), needs `transaction`.
+    pub fn lock_in_share_mode(mut self) -> Self {
+        self.lock = Some(Lock::new(LockMode::ShareMode, None));
+        self
+    }
+
+    /// `SKIP LOCKED`, `FOR UPDATE` unless `for_share` is used.
+    pub fn skip_locked(mut self) -> Self {
+        self.lock = Some(self.lock_mode().with_wait(LockWait::SkipLocked));
+        self
+    }
+
+    /// `NOWAIT`, `FOR UPDATE` unless `for_share` is used.
+    pub fn nowait(mut self) -> Self {
+        self.lock = Some(self.lock_mode().with_wait(LockWait::Nowait));
+        self
+    }
+
+    fn lock_wait(&self) -> Option<LockWait> {
+        self.lock.as_ref().and_then(|lock| lock.wait)
+    }
+
+    fn lock_mode(&self) -> Lock {
+        self.lock
+            .unwrap_or_else(|| Lock::new(LockMode::Update, None))
+    }
+
     pub fn order_asc_field(mut self, field: &'a str) -> Self {
         self.order.push(Order::new(field, true));
         self
@@ -174,110 +434,611 @@ impl<'a, 'd, E: Entity + for<'r> FromRow<'r, MySqlRow> + Send + Unpin> QueryWrap
 
     pub fn select_field(mut self, field: &[&'a str]) -> Self {
         for &f in field {
-            self.field.push(f);
+            self.field.push(Expr::raw(f));
+        }
+        self
+    }
+
+    /// `expr AS alias`, decode the rows with `vec_as`.
+    pub fn select_expr(mut self, expr: Expr, alias: &str) -> Self {
+        let (sql, values) = expr.into_parts();
+        self.field
+            .push(Expr::raw_values(&format!("{sql} AS {alias}"), values));
+        self
+    }
+
+    /// selects the full-text relevance score as `alias` and orders by it, most relevant first.
+    ///
+    /// combine with `match_against` on the same fields to skip the rows that don't match.
+    pub fn select_relevance<F>(
+        self,
+        fields: F,
+        query: &str,
+        mode: MatchMode,
+        alias: &'a str,
+    ) -> Self
+    where
+        F: IntoFields<'a, E>,
+    {
+        self.select_relevance_field(fields.into_fields(), query, mode, alias)
+    }
+
+    pub fn select_relevance_field(
+        mut self,
+        fields: Vec<&'a str>,
+        query: &str,
+        mode: MatchMode,
+        alias: &'a str,
+    ) -> Self {
+        self.select_all_if_empty();
+        self.select_expr(Expr::match_against(&fields, query, mode), alias)
+            .order_desc_field(alias)
+    }
+
+    /// keeps the entity columns when an expression column is added to an empty select.
+    fn select_all_if_empty(&mut self) {
+        if self.field.is_empty() {
+            let table = self.from.as_deref().unwrap_or(E::table_name()).to_string();
+            self.field.push(Expr::raw(&format!("{table}.*")));
+        }
+    }
+
+    /// first `n` rows of every `partition_field` group in `order`, ranked by `ROW_NUMBER()`.
+    ///
+    /// the wrapper's select list, conditions and grouping select the ranked rows, the result is
+    /// ordered by group and rank. limits, locks, optimizer hints, first/last and comment apply to the
+    /// outer query.
+    pub fn top_n_per_group<F>(mut self, partition_field: F, order: OrderBy, n: u64) -> Self
+    where
+        F: IntoField<'a, E>,
+    {
+        let partition_field = partition_field.into_field();
+        let row_number = Expr::row_number().over(
+            Window::new()
+                .partition_by(Expr::raw(partition_field))
+                .order_by(order),
+        );
+        self.select_all_if_empty();
+        let mut ranked = self.select_expr(row_number, "row_num");
+        let first = ranked.first.take();
+        let hints = ranked.hints.take_optimizer();
+        let sql = ranked.select_sql();
+        // inner CTEs move to the outer WITH
+        let mut ctes = std::mem::take(&mut ranked.ctes);
+        ctes.push(Cte::new(
+            "ranked".into(),
+            sql,
+            ranked.statement_values(true),
+        ));
+        let invalid = ranked.invalid().map(str::to_string);
+        let mut query = QueryWrapper::with_executor(ranked.db);
+        query.ctes = ctes;
+        query.recursive = ranked.recursive;
+        query.from = Some("ranked".into());
+        query.hints = hints;
+        query.limit = ranked.limit;
+        query.offset = ranked.offset;
+        query.lock = ranked.lock;
+        query.first = first;
+        query.last = ranked.last;
+        query.comment = ranked.comment;
+        query.invalid = invalid;
+        query
+            .le_field("row_num", n)
+            .order_asc_field(partition_field)
+            .order_asc_field("row_num")
+    }
+
+    pub fn select_exclude_field(mut self, exclude: &[&str]) -> Self {
+        for column in E::columns() {
+            if !column.skip && !exclude.contains(&column.name) {
+                self.field.push(Expr::raw(column.name));
+            }
         }
         self
     }
 
+    pub fn select_as_field(mut self, field: &'a str, alias: &'a str) -> Self {
+        self.field.push(Expr::raw(&format!("{field} AS {alias}")));
+        self
+    }
+
+    /// select from a CTE, a derived table or a join instead of the entity table.
+    pub fn from(mut self, from: &'a str) -> Self {
+        self.from = Some(from.into());
+        self
+    }
+
+    /// `WITH name AS (query)`, select from it with `from`.
+    pub fn with<O>(mut self, name: &'a str, mut query: QueryWrapper<'a, '_, O>) -> Self
+    where
+        O: Entity + for<'r> FromRow<'r, MySqlRow> + Send + Unpin,
+    {
+        let sql = query.sql();
+        self.ctes.push(Cte::new(name.into(), sql, query.values()));
+        self.invalid = self.invalid.or_else(|| query.invalid().map(str::to_string));
+        self
+    }
+
+    /// `WITH name AS (sql)`, use `?` as placeholders.
+    pub fn with_raw<V>(mut self, name: &'a str, sql: &str, values: Vec<V>) -> Self
+    where
+        V: Into<SqlValue> + Clone,
+    {
+        let values = values.into_iter().map(Into::into).collect();
+        self.ctes
+            .push(Cte::new(name.into(), sql.to_string(), values));
+        self
+    }
+
+    /// `WITH RECURSIVE name AS (anchor UNION ALL recursive)`, the recursive wrapper
+    /// joins the CTE through `from`.
+    pub fn with_recursive<O>(mut self, name: &'a str, query: UnionQuery<'a, '_, O>) -> Self
+    where
+        O: Entity + for<'r> FromRow<'r, MySqlRow> + Send + Unpin,
+    {
+        self.recursive = true;
+        self.ctes
+            .push(Cte::new(name.into(), query.sql(), query.values()));
+        self.invalid = self.invalid.or(query.invalid);
+        self
+    }
+
+    /// `WITH RECURSIVE name AS (sql)`, use `?` as placeholders.
+    pub fn with_recursive_raw<V>(self, name: &'a str, sql: &str, values: Vec<V>) -> Self
+    where
+        V: Into<SqlValue> + Clone,
+    {
+        let mut query = self.with_raw(name, sql, values);
+        query.recursive = true;
+        query
+    }
+
+    /// all rows below `id` in the tree of `parent_field`, `id` itself excluded.
+    ///
+    /// conditions, ordering and limits apply to the descendants.
+    pub fn descendants<F, P, V>(self, id_field: F, parent_field: P, id: V) -> Self
+    where
+        F: IntoField<'a, E>,
+        P: IntoField<'a, E>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
+    {
+        let (id_field, parent_field) = (id_field.into_field(), parent_field.into_field());
+        let table = E::table_name();
+        let sql = format!(
+            "SELECT * FROM {table} WHERE {parent_field} = ? \
+            UNION ALL \
+            SELECT t.* FROM {table} AS t JOIN {table}_tree ON t.{parent_field} = {table}_tree.{id_field}"
+        );
+        self.tree(sql, id.into())
+    }
+
+    /// all rows above `id` in the tree of `parent_field`, `id` itself excluded.
+    ///
+    /// conditions, ordering and limits apply to the ancestors.
+    pub fn ancestors<F, P, V>(self, id_field: F, parent_field: P, id: V) -> Self
+    where
+        F: IntoField<'a, E>,
+        P: IntoField<'a, E>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
+    {
+        let (id_field, parent_field) = (id_field.into_field(), parent_field.into_field());
+        let table = E::table_name();
+        let sql = format!(
+            "SELECT p.* FROM {table} AS p JOIN {table} AS c ON p.{id_field} = c.{parent_field} WHERE c.{id_field} = ? \
+            UNION ALL \
+            SELECT t.* FROM {table} AS t JOIN {table}_tree ON t.{id_field} = {table}_tree.{parent_field}"
+        );
+        self.tree(sql, id.into())
+    }
+
+    /// `WITH RECURSIVE {table}_tree AS (sql) SELECT .. FROM {table}_tree`
+    fn tree(mut self, sql: String, id: SqlValue) -> Self {
+        let name = format!("{}_tree", E::table_name());
+        self.recursive = true;
+        self.ctes.push(Cte::new(name.clone().into(), sql, vec![id]));
+        self.from = Some(name.into());
+        self
+    }
+
+    /// `(self) UNION (other)`, `other` may query another entity with compatible columns.
+    pub fn union<O>(self, other: QueryWrapper<'a, '_, O>) -> UnionQuery<'a, 'd, E>
+    where
+        O: Entity + for<'r> FromRow<'r, MySqlRow> + Send + Unpin,
+    {
+        UnionQuery::new(self).union(other)
+    }
+
+    /// `(self) UNION ALL (other)`, duplicates are kept.
+    pub fn union_all<O>(self, other: QueryWrapper<'a, '_, O>) -> UnionQuery<'a, 'd, E>
+    where
+        O: Entity + for<'r> FromRow<'r, MySqlRow> + Send + Unpin,
+    {
+        UnionQuery::new(self).union_all(other)
+    }
+
     pub fn sql(&mut self) -> String {
         let mut sql = String::new();
-        if let Some(first) = self.first {
-            sql += first;
+        if let Some(first) = &self.first {
+            sql += first.sql;
             sql += " ";
         }
-        sql += "SELECT ";
+        sql += &self.with_sql();
+        sql += &self.select_sql();
+        sql += &order_limit_sql(&self.order, self.limit, self.offset);
+        if let Some(lock) = &self.lock {
+            sql += &lock.to_sql();
+        }
+        if let Some(last) = &self.last {
+            sql += &format!(" {}", last.sql);
+        }
+        if let Some(comment) = self.comment {
+            sql += &comment_sql(comment);
+        }
+        sql
+    }
+
+    /// `SELECT .. FROM .. WHERE .. GROUP BY .. HAVING ..`
+    fn select_sql(&mut self) -> String {
+        let mut sql = format!("SELECT {}", self.hints.optimizer_sql());
         if self.field.is_empty() {
             sql += "*";
         } else {
             sql += self
                 .field
                 .iter()
-                .map(|f| f.to_string())
+                .map(|f| f.sql())
                 .collect::<Vec<_>>()
                 .join(", ")
                 .as_str();
         };
-        sql += &format!(" FROM {}", E::table_name());
+        sql += &format!(" FROM {}", self.from.as_deref().unwrap_or(E::table_name()));
+        // MySQL rejects index hints on a CTE
+        if !self.selects_from_cte() {
+            sql += &self.hints.index_sql();
+        }
         sql += self.r#where().as_str();
-        if !self.order.is_empty() {
-            sql += " ORDER BY ";
-            let orders: Vec<String> = self
-                .order
-                .iter()
-                .map(|o| {
-                    if o.asc_desc {
-                        format!("{} ASC", o.field)
-                    } else {
-                        format!("{} DESC", o.field)
-                    }
-                })
-                .collect();
+        sql += self.group_having.to_sql().as_str();
+        sql
+    }
+
+    /// whether `from` names one of the CTEs, with or without an alias.
+    fn selects_from_cte(&self) -> bool {
+        self.from
+            .as_deref()
+            .and_then(|from| from.split_whitespace().next())
+            .is_some_and(|name| self.ctes.iter().any(|cte| cte.name == name))
+    }
 
-            sql += &orders.join(", ");
+    /// parenthesized when the part has its own ORDER BY, LIMIT, lock or CTEs.
+    fn union_part_sql(&mut self) -> String {
+        let sql = self.sql();
+        let plain = self.order.is_empty()
+            && self.limit.is_none()
+            && self.offset.is_none()
+            && self.lock.is_none()
+            && self.ctes.is_empty();
+        if plain { sql } else { format!("({sql})") }
+    }
+
+    /// first error of a union part, `first` and `last` would end up inside the part.
+    fn union_part_invalid(&self) -> Option<String> {
+        if self.first.is_some() || self.last.is_some() {
+            return Some(format!(
+                "first/last can't be used on a union part of {}",
+                E::table_name()
+            ));
+        }
+        self.invalid().map(str::to_string)
+    }
+
+    /// `WITH [RECURSIVE] a AS (..), b AS (..) `
+    fn with_sql(&self) -> String {
+        if self.ctes.is_empty() {
+            return String::new();
+        }
+        let ctes = self
+            .ctes
+            .iter()
+            .map(|cte| format!("{} AS ({})", cte.name, cte.sql))
+            .collect::<Vec<_>>()
+            .join(", ");
+        if self.recursive {
+            format!("WITH RECURSIVE {ctes} ")
+        } else {
+            format!("WITH {ctes} ")
         }
-        if let Some(last) = self.last {
-            sql += &format!(" {last}");
+    }
+
+    /// total rows of a page, grouped queries are counted as a derived table.
+    fn count_sql(&mut self) -> String {
+        let mut sql = String::new();
+        if let Some(first) = &self.first {
+            sql += first.sql;
+            sql += " ";
+        }
+        sql += &self.with_sql();
+        if self.group_having.is_empty() {
+            let field = std::mem::replace(&mut self.field, vec![Expr::count_all()]);
+            sql += &self.select_sql();
+            self.field = field;
+        } else {
+            sql += &format!("SELECT COUNT(*) FROM ({}) AS page_count", self.select_sql());
         }
         if let Some(comment) = self.comment {
-            sql += &format!(" -- {comment}");
+            sql += &comment_sql(comment);
         }
         sql
     }
 
-    async fn aggregation(mut self) -> Result<Option<i64>> {
+    fn values(&self) -> Vec<SqlValue> {
+        let mut values = self.statement_values(true);
+        values.extend(limit_values(self.limit, self.offset));
+        if let Some(last) = &self.last {
+            values.extend(last.values.iter().cloned());
+        }
+        values
+    }
+
+    /// locking reads outside a transaction release their locks right away, they are rejected.
+    fn check_lock(&self) -> Result<()> {
+        if self.lock.is_some() && matches!(self.db, Executor::Pool(_)) {
+            return Err(Error::InvalidArgument(format!(
+                "locking read of {} needs a transaction, call transaction(&mut tx) first",
+                E::table_name()
+            )));
+        }
+        Ok(())
+    }
+
+    /// first error of the conditions and embedded queries.
+    fn invalid(&self) -> Option<&str> {
+        self.invalid
+            .as_deref()
+            .or_else(|| invalid_where(&self.wheres))
+    }
+
+    fn arguments(&self) -> Result<MySqlArguments> {
+        self.check_lock()?;
+        check_invalid(self.invalid())?;
+        arguments(&self.values())
+    }
+
+    fn count_arguments(&self) -> Result<MySqlArguments> {
+        self.check_lock()?;
+        check_invalid(self.invalid())?;
+        arguments(&self.count_values())
+    }
+
+    /// the select list is replaced by `COUNT(*)` unless the query is grouped.
+    fn count_values(&self) -> Vec<SqlValue> {
+        self.statement_values(!self.group_having.is_empty())
+    }
+
+    /// values up to HAVING.
+    fn statement_values(&self, select: bool) -> Vec<SqlValue> {
+        let mut values = vec![];
+        if let Some(first) = &self.first {
+            values.extend(first.values.iter().cloned());
+        }
+        for cte in &self.ctes {
+            values.extend(cte.values.iter().cloned());
+        }
+        if select {
+            for field in &self.field {
+                values.extend(field.values().iter().cloned());
+            }
+        }
+        for r#where in &self.wheres {
+            values.extend(r#where.values().iter().cloned());
+        }
+        values.extend(self.group_having.values().iter().cloned());
+        values
+    }
+
+    /// `limit` replaces the wrapper's limit, offset is dropped.
+    async fn aggregation(mut self, limit: Option<u64>) -> Result<Option<i64>> {
         self.order.clear();
+        self.limit = limit;
+        self.offset = None;
         let sql = self.sql();
-        self.bind_query_scalar(sqlx::query_scalar::<MySql, i64>(&sql))
-            .fetch_optional(self.db)
-            .await
+        execute!(
+            self.db,
+            sqlx::query_scalar_with::<MySql, i64, _>(&sql, self.arguments()?),
+            fetch_optional
+        )
     }
 
     pub async fn exists(mut self) -> Result<bool> {
-        self.field = vec!["1"];
-        Ok(self.aggregation().await?.is_some())
+        self.field = vec![Expr::raw("1")];
+        Ok(self.aggregation(Some(1)).await?.is_some())
     }
 
     pub async fn count(mut self) -> Result<Option<i64>> {
-        self.field = vec!["COUNT(*)"];
-        self.aggregation().await
+        self.field = vec![Expr::count_all()];
+        self.aggregation(None).await
     }
 
     pub async fn sum(mut self, field: &'a str) -> Result<Option<i64>> {
-        let field = format!("SUM({})", field);
-        self.field = vec![&field];
-        self.aggregation().await
+        self.field = vec![Expr::func("SUM", vec![Expr::raw(field)])];
+        self.aggregation(None).await
     }
 
     pub async fn max(mut self, field: &'a str) -> Result<Option<i64>> {
-        let field = format!("MAX({})", field);
-        self.field = vec![&field];
-        self.aggregation().await
+        self.field = vec![Expr::func("MAX", vec![Expr::raw(field)])];
+        self.aggregation(None).await
     }
 
     pub async fn min(mut self, field: &'a str) -> Result<Option<i64>> {
-        let field = format!("MIN({})", field);
-        self.field = vec![&field];
-        self.aggregation().await
+        self.field = vec![Expr::func("MIN", vec![Expr::raw(field)])];
+        self.aggregation(None).await
     }
 
     pub async fn vec(mut self) -> Result<Vec<E>> {
         let sql = self.sql();
-        self.bind_query_as::<E>(sqlx::query_as(&sql))
-            .fetch_all(self.db)
-            .await
+        execute!(
+            self.db,
+            sqlx::query_as_with::<MySql, E, _>(&sql, self.arguments()?),
+            fetch_all
+        )
+    }
+
+    pub async fn opt(self) -> Result<Option<E>> {
+        self.opt_as::<E>().await
+    }
+
+    /// `LIMIT 1`, the first row in the wrapper's order.
+    pub async fn first_row(mut self) -> Result<Option<E>> {
+        self.limit = Some(1);
+        self.opt_as::<E>().await
     }
 
-    pub async fn opt(mut self) -> Result<Option<E>> {
+    /// errors when more than one row matches.
+    pub async fn one(mut self) -> Result<Option<E>> {
+        self.limit = Some(2);
+        let mut rows = self.vec().await?;
+        if rows.len() > 1 {
+            return Err(Error::InvalidArgument(format!(
+                "one() expected at most one row of {}, found more",
+                E::table_name()
+            )));
+        }
+        Ok(rows.pop())
+    }
+
+    /// `current` starts from 1.
+    pub async fn page(self, current: u64, size: u64) -> Result<Page<E>> {
+        self.page_as::<E>(current, size).await
+    }
+
+    /// decode rows into `T` instead of the entity, e.g. a DTO of the selected columns.
+    pub async fn vec_as<T>(mut self) -> Result<Vec<T>>
+    where
+        T: for<'r> FromRow<'r, MySqlRow> + Send + Unpin,
+    {
         let sql = self.sql();
-        self.bind_query_as::<E>(sqlx::query_as(&sql))
-            .fetch_optional(self.db)
-            .await
+        execute!(
+            self.db,
+            sqlx::query_as_with::<MySql, T, _>(&sql, self.arguments()?),
+            fetch_all
+        )
+    }
+
+    pub async fn opt_as<T>(mut self) -> Result<Option<T>>
+    where
+        T: for<'r> FromRow<'r, MySqlRow> + Send + Unpin,
+    {
+        let sql = self.sql();
+        execute!(
+            self.db,
+            sqlx::query_as_with::<MySql, T, _>(&sql, self.arguments()?),
+            fetch_optional
+        )
+    }
+
+    /// `current` starts from 1, records aren't queried when the page is past the total.
+    pub async fn page_as<T>(mut self, current: u64, size: u64) -> Result<Page<T>>
+    where
+        T: for<'r> FromRow<'r, MySqlRow> + Send + Unpin,
+    {
+        let current = current.max(1);
+        let count_sql = self.count_sql();
+        let total = execute!(
+            self.db,
+            sqlx::query_scalar_with::<MySql, i64, _>(&count_sql, self.count_arguments()?),
+            fetch_one
+        )? as u64;
+        let offset = (current - 1).saturating_mul(size);
+        let records = if total > offset && size > 0 {
+            self.limit = Some(size);
+            self.offset = Some(offset);
+            self.vec_as::<T>().await?
+        } else {
+            vec![]
+        };
+        Ok(Page {
+            records,
+            total,
+            current,
+            size,
+        })
+    }
+
+    /// values of a single column, e.g. `list_column::<u64>(User::id_field)`.
+    pub async fn list_column<T>(self, field_func: impl IntoField<'a, E>) -> Result<Vec<T>>
+    where
+        T: for<'r> Decode<'r, MySql> + Type<MySql> + Send + Unpin,
+    {
+        self.list_column_field::<T>(field_func.into_field()).await
+    }
+
+    pub async fn list_column_field<T>(mut self, field: &'a str) -> Result<Vec<T>>
+    where
+        T: for<'r> Decode<'r, MySql> + Type<MySql> + Send + Unpin,
+    {
+        self.field = vec![Expr::raw(field)];
+        let sql = self.sql();
+        execute!(
+            self.db,
+            sqlx::query_scalar_with::<MySql, T, _>(&sql, self.arguments()?),
+            fetch_all
+        )
+    }
+
+    /// entities keyed by a column, later rows replace earlier rows with the same key.
+    ///
+    /// the key is read from the output column, `u.id` reads `id` and `u.id AS uid` reads `uid`,
+    /// it must be selected.
+    pub async fn map_by<K>(self, key_field_func: impl IntoField<'a, E>) -> Result<HashMap<K, E>>
+    where
+        K: for<'r> Decode<'r, MySql> + Type<MySql> + Eq + Hash,
+    {
+        self.map_by_field::<K>(key_field_func.into_field()).await
+    }
+
+    pub async fn map_by_field<K>(mut self, key_field: &'a str) -> Result<HashMap<K, E>>
+    where
+        K: for<'r> Decode<'r, MySql> + Type<MySql> + Eq + Hash,
+    {
+        let key = output_name(key_field);
+        if !self.selects(key) {
+            return Err(Error::InvalidArgument(format!(
+                "map_by key {key} is not selected from {}",
+                E::table_name()
+            )));
+        }
+        let sql = self.sql();
+        let rows = execute!(
+            self.db,
+            sqlx::query_with(&sql, self.arguments()?),
+            fetch_all
+        )?;
+        let mut map = HashMap::with_capacity(rows.len());
+        for row in rows {
+            map.insert(row.try_get::<K, _>(key)?, E::from_row(&row)?);
+        }
+        Ok(map)
+    }
+
+    /// whether the output column `name` is selected, `*` selects the entity columns.
+    fn selects(&self, name: &str) -> bool {
+        if self.field.is_empty() {
+            return self.from.is_some()
+                || E::columns().is_empty()
+                || E::column(name).is_some_and(|column| !column.skip && column.name == name);
+        }
+        self.field.iter().any(|field| {
+            let output = output_name(field.sql());
+            output == name || output == "*"
+        })
     }
 }
 
 impl<'a, 'd, E: Entity + for<'r> FromRow<'r, MySqlRow> + Send + Unpin> Wrapper<'a>
     for QueryWrapper<'a, 'd, E>
 {
+    type Entity = E;
+
     fn wheres(&self) -> &Vec<Where<'a>> {
         &self.wheres
     }
@@ -302,19 +1063,21 @@ impl<'a, 'd, E: Entity + for<'r> FromRow<'r, MySqlRow> + Send + Unpin> Wrapper<'
         &mut self.bracket
     }
 
-    fn first(mut self, sql: &'a str) -> Self
+    fn first_values<V>(mut self, sql: &'a str, values: Vec<V>) -> Self
     where
+        V: Into<SqlValue> + Clone,
         Self: Sized,
     {
-        self.first = Some(sql);
+        self.first = Some(Clause::new(sql, values));
         self
     }
 
-    fn last(mut self, sql: &'a str) -> Self
+    fn last_values<V>(mut self, sql: &'a str, values: Vec<V>) -> Self
     where
+        V: Into<SqlValue> + Clone,
         Self: Sized,
     {
-        self.last = Some(sql);
+        self.last = Some(Clause::new(sql, values));
         self
     }
 
@@ -327,19 +1090,236 @@ impl<'a, 'd, E: Entity + for<'r> FromRow<'r, MySqlRow> + Send + Unpin> Wrapper<'
     }
 }
 
-pub struct UpdateWrapper<'a, 'd, E>
+/// named subquery of `WITH`.
+struct Cte<'a> {
+    name: Cow<'a, str>,
+    sql: String,
+    values: Vec<SqlValue>,
+}
+
+impl<'a> Cte<'a> {
+    fn new(name: Cow<'a, str>, sql: String, values: Vec<SqlValue>) -> Self {
+        Self { name, sql, values }
+    }
+}
+
+/// `(a) UNION (b) ..` of query wrappers, rows decode into `E` of the first wrapper.
+///
+/// ordering, limits and pages apply to the combined rows, use the column names of the first wrapper.
+/// `first` and `last` of a part fail with `InvalidArgument`, a locking part needs `transaction`.
+pub struct UnionQuery<'a, 'd, E>
 where
     E: Entity + for<'r> FromRow<'r, MySqlRow> + Send + Unpin,
 {
-    set_key: Vec<&'a str>,
-    set_value: Vec<SqlValue>,
-    wheres: Vec<Where<'a>>,
-    or_index: HashSet<usize>,
-    bracket: Bracket,
-    first: Option<&'a str>,
-    last: Option<&'a str>,
-    comment: Option<&'a str>,
-    db: &'d MySqlPool,
+    sql: String,
+    values: Vec<SqlValue>,
+    order: Vec<Order<'a>>,
+    limit: Option<u64>,
+    offset: Option<u64>,
+    /// first error of the parts, reported when the statement runs.
+    invalid: Option<String>,
+    /// a part is a locking read, it needs a transaction like `QueryWrapper::for_update`.
+    locked: bool,
+    db: Executor<'d>,
+    _ignore: PhantomData<E>,
+}
+
+impl<'a, 'd, E: Entity + for<'r> FromRow<'r, MySqlRow> + Send + Unpin> UnionQuery<'a, 'd, E> {
+    fn new(mut query: QueryWrapper<'a, 'd, E>) -> Self {
+        Self {
+            sql: query.union_part_sql(),
+            values: query.values(),
+            order: vec![],
+            limit: None,
+            offset: None,
+            invalid: query.union_part_invalid(),
+            locked: query.lock.is_some(),
+            db: query.db,
+            _ignore: Default::default(),
+        }
+    }
+
+    fn push<O>(mut self, mut query: QueryWrapper<'a, '_, O>, union: &str) -> Self
+    where
+        O: Entity + for<'r> FromRow<'r, MySqlRow> + Send + Unpin,
+    {
+        self.sql += &format!(" {union} {}", query.union_part_sql());
+        self.values.extend(query.values());
+        self.invalid = self.invalid.or_else(|| query.union_part_invalid());
+        self.locked |= query.lock.is_some();
+        self
+    }
+
+    pub fn union<O>(self, query: QueryWrapper<'a, '_, O>) -> Self
+    where
+        O: Entity + for<'r> FromRow<'r, MySqlRow> + Send + Unpin,
+    {
+        self.push(query, "UNION")
+    }
+
+    pub fn union_all<O>(self, query: QueryWrapper<'a, '_, O>) -> Self
+    where
+        O: Entity + for<'r> FromRow<'r, MySqlRow> + Send + Unpin,
+    {
+        self.push(query, "UNION ALL")
+    }
+
+    /// run on the connection of `tx` instead of the pool.
+    pub fn transaction(mut self, tx: &'d mut Transaction<'_, MySql>) -> Self {
+        self.db = Executor::Transaction(tx);
+        self
+    }
+
+    pub fn order_asc<F>(self, field_func: F) -> Self
+    where
+        F: IntoField<'a, E>,
+    {
+        self.order_asc_field(field_func.into_field())
+    }
+
+    pub fn order_desc<F>(self, field_func: F) -> Self
+    where
+        F: IntoField<'a, E>,
+    {
+        self.order_desc_field(field_func.into_field())
+    }
+
+    pub fn order_asc_field(mut self, field: &'a str) -> Self {
+        self.order.push(Order::new(field, true));
+        self
+    }
+
+    pub fn order_desc_field(mut self, field: &'a str) -> Self {
+        self.order.push(Order::new(field, false));
+        self
+    }
+
+    pub fn limit(mut self, limit: u64) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn offset(mut self, offset: u64) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    pub fn sql(&self) -> String {
+        format!(
+            "{}{}",
+            self.sql,
+            order_limit_sql(&self.order, self.limit, self.offset)
+        )
+    }
+
+    fn values(&self) -> Vec<SqlValue> {
+        let mut values = self.values.clone();
+        values.extend(limit_values(self.limit, self.offset));
+        values
+    }
+
+    fn arguments(&self, values: &[SqlValue]) -> Result<MySqlArguments> {
+        if self.locked && matches!(self.db, Executor::Pool(_)) {
+            return Err(Error::InvalidArgument(format!(
+                "locking read in a union of {} needs a transaction, call transaction(&mut tx) first",
+                E::table_name()
+            )));
+        }
+        check_invalid(self.invalid.as_deref())?;
+        arguments(values)
+    }
+
+    pub async fn count(mut self) -> Result<i64> {
+        let sql = format!("SELECT COUNT(*) FROM ({}) AS union_count", self.sql);
+        execute!(
+            self.db,
+            sqlx::query_scalar_with::<MySql, i64, _>(&sql, self.arguments(&self.values)?),
+            fetch_one
+        )
+    }
+
+    pub async fn vec(self) -> Result<Vec<E>> {
+        self.vec_as::<E>().await
+    }
+
+    pub async fn opt(self) -> Result<Option<E>> {
+        self.opt_as::<E>().await
+    }
+
+    /// `current` starts from 1.
+    pub async fn page(self, current: u64, size: u64) -> Result<Page<E>> {
+        self.page_as::<E>(current, size).await
+    }
+
+    pub async fn vec_as<T>(mut self) -> Result<Vec<T>>
+    where
+        T: for<'r> FromRow<'r, MySqlRow> + Send + Unpin,
+    {
+        let sql = self.sql();
+        execute!(
+            self.db,
+            sqlx::query_as_with::<MySql, T, _>(&sql, self.arguments(&self.values())?),
+            fetch_all
+        )
+    }
+
+    pub async fn opt_as<T>(mut self) -> Result<Option<T>>
+    where
+        T: for<'r> FromRow<'r, MySqlRow> + Send + Unpin,
+    {
+        let sql = self.sql();
+        execute!(
+            self.db,
+            sqlx::query_as_with::<MySql, T, _>(&sql, self.arguments(&self.values())?),
+            fetch_optional
+        )
+    }
+
+    /// `current` starts from 1, records aren't queried when the page is past the total.
+    pub async fn page_as<T>(mut self, current: u64, size: u64) -> Result<Page<T>>
+    where
+        T: for<'r> FromRow<'r, MySqlRow> + Send + Unpin,
+    {
+        let current = current.max(1);
+        let count_sql = format!("SELECT COUNT(*) FROM ({}) AS union_count", self.sql);
+        let total = execute!(
+            self.db,
+            sqlx::query_scalar_with::<MySql, i64, _>(&count_sql, self.arguments(&self.values)?),
+            fetch_one
+        )? as u64;
+        let offset = (current - 1).saturating_mul(size);
+        let records = if total > offset && size > 0 {
+            self.limit = Some(size);
+            self.offset = Some(offset);
+            self.vec_as::<T>().await?
+        } else {
+            vec![]
+        };
+        Ok(Page {
+            records,
+            total,
+            current,
+            size,
+        })
+    }
+}
+
+pub struct UpdateWrapper<'a, 'd, E>
+where
+    E: Entity + for<'r> FromRow<'r, MySqlRow> + Send + Unpin,
+{
+    set_key: Vec<&'a str>,
+    set_value: Vec<Expr>,
+    hints: Hints<'a>,
+    wheres: Vec<Where<'a>>,
+    or_index: HashSet<usize>,
+    bracket: Bracket,
+    order: Vec<Order<'a>>,
+    limit: Option<u64>,
+    first: Option<Clause<'a>>,
+    last: Option<Clause<'a>>,
+    comment: Option<&'a str>,
+    db: Executor<'d>,
     _ignore: PhantomData<E>,
 }
 
@@ -348,29 +1328,92 @@ impl<'a, 'd, E: Entity + for<'r> FromRow<'r, MySqlRow> + Send + Unpin> UpdateWra
         Self {
             set_key: Vec::new(),
             set_value: Vec::new(),
+            hints: Hints::default(),
             wheres: vec![],
             or_index: HashSet::new(),
             bracket: Bracket::new(),
+            order: vec![],
+            limit: None,
             first: None,
             last: None,
             comment: None,
-            db,
+            db: Executor::Pool(db),
             _ignore: Default::default(),
         }
     }
 
+    /// run on the connection of `tx` instead of the pool.
+    pub fn transaction(mut self, tx: &'d mut Transaction<'_, MySql>) -> Self {
+        self.db = Executor::Transaction(tx);
+        self
+    }
+
+    pub fn order_asc<F>(self, field_func: F) -> Self
+    where
+        F: IntoField<'a, E>,
+    {
+        self.order_asc_field(field_func.into_field())
+    }
+
+    pub fn order_desc<F>(self, field_func: F) -> Self
+    where
+        F: IntoField<'a, E>,
+    {
+        self.order_desc_field(field_func.into_field())
+    }
+
+    pub fn order_asc_field(mut self, field: &'a str) -> Self {
+        self.order.push(Order::new(field, true));
+        self
+    }
+
+    pub fn order_desc_field(mut self, field: &'a str) -> Self {
+        self.order.push(Order::new(field, false));
+        self
+    }
+
+    /// `LIMIT ?`, at most `limit` rows, in `order` when given.
+    pub fn limit(mut self, limit: u64) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// `USE INDEX (a, b)` after the table name.
+    pub fn use_index(mut self, indexes: &'a [&'a str]) -> Self {
+        self.hints.push_index(IndexHint::Use, indexes);
+        self
+    }
+
+    /// `FORCE INDEX (a, b)` after the table name.
+    pub fn force_index(mut self, indexes: &'a [&'a str]) -> Self {
+        self.hints.push_index(IndexHint::Force, indexes);
+        self
+    }
+
+    /// `IGNORE INDEX (a, b)` after the table name.
+    pub fn ignore_index(mut self, indexes: &'a [&'a str]) -> Self {
+        self.hints.push_index(IndexHint::Ignore, indexes);
+        self
+    }
+
+    /// MySQL 8 optimizer hint in `/*+ .. */` after the statement keyword, e.g. `BKA(user)`.
+    pub fn hint(mut self, hint: &'a str) -> Self {
+        self.hints.push_optimizer(hint);
+        self
+    }
+
     pub fn set<F, V>(self, field_func: F, value: V) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, E>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
     {
-        self.set_field(*field_func(), value)
+        self.set_field(field_func.into_field(), value)
     }
 
     pub fn set_flag<F, V>(mut self, field_func: F, value: V, flag: bool) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, E>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
     {
         if flag {
             self = self.set(field_func, value);
@@ -380,107 +1423,604 @@ impl<'a, 'd, E: Entity + for<'r> FromRow<'r, MySqlRow> + Send + Unpin> UpdateWra
 
     pub fn set_opt<F, V>(mut self, field_func: F, value: Option<V>) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, E>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
+    {
+        if let Some(value) = value {
+            self = self.set_field(field_func.into_field(), value);
+        }
+        self
+    }
+
+    pub fn set_opt_flag<F, V>(mut self, field_func: F, value: Option<V>, flag: bool) -> Self
+    where
+        F: IntoField<'a, E>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
+    {
+        if flag {
+            self = self.set_opt(field_func, value);
+        }
+        self
+    }
+
+    pub fn set_field<V>(self, field: &'a str, value: V) -> Self
+    where
+        V: Into<SqlValue> + Clone,
+    {
+        self.set_expr_field(field, Expr::value(value))
+    }
+
+    pub fn set_field_flag<V>(mut self, field: &'a str, value: V, flag: bool) -> Self
+    where
+        V: Into<SqlValue> + Clone,
+    {
+        if flag {
+            self = self.set_field(field, value);
+        }
+        self
+    }
+
+    pub fn set_field_opt<V>(mut self, field: &'a str, value: Option<V>) -> Self
+    where
+        V: Into<SqlValue> + Clone,
+    {
+        if let Some(value) = value {
+            self = self.set_field(field, value);
+        }
+        self
+    }
+
+    pub fn set_field_opt_flag<V>(mut self, field: &'a str, value: Option<V>, flag: bool) -> Self
+    where
+        V: Into<SqlValue> + Clone,
+    {
+        if flag {
+            self = self.set_field_opt(field, value);
+        }
+        self
+    }
+
+    /// `field = JSON_SET(field, path, ?)`, JSON documents are set as objects/arrays, not strings.
+    pub fn json_set<F, V>(self, field_func: F, path: &str, value: V) -> Self
+    where
+        F: IntoField<'a, E>,
+        V: Into<SqlValue> + Clone,
+    {
+        self.json_set_field(field_func.into_field(), path, value)
+    }
+
+    pub fn json_set_flag<F, V>(mut self, field_func: F, path: &str, value: V, flag: bool) -> Self
+    where
+        F: IntoField<'a, E>,
+        V: Into<SqlValue> + Clone,
+    {
+        if flag {
+            self = self.json_set(field_func, path, value);
+        }
+        self
+    }
+
+    pub fn json_set_opt<F, V>(mut self, field_func: F, path: &str, value: Option<V>) -> Self
+    where
+        F: IntoField<'a, E>,
+        V: Into<SqlValue> + Clone,
+    {
+        if let Some(value) = value {
+            self = self.json_set_field(field_func.into_field(), path, value);
+        }
+        self
+    }
+
+    pub fn json_set_opt_flag<F, V>(
+        mut self,
+        field_func: F,
+        path: &str,
+        value: Option<V>,
+        flag: bool,
+    ) -> Self
+    where
+        F: IntoField<'a, E>,
+        V: Into<SqlValue> + Clone,
+    {
+        if flag {
+            self = self.json_set_opt(field_func, path, value);
+        }
+        self
+    }
+
+    pub fn json_set_field<V>(self, field: &'a str, path: &str, value: V) -> Self
+    where
+        V: Into<SqlValue> + Clone,
+    {
+        let json = Expr::func(
+            "JSON_SET",
+            vec![Expr::raw(field), Expr::value(path), Expr::json(value)],
+        );
+        self.set_expr_field(field, json)
+    }
+
+    pub fn json_set_field_flag<V>(
+        mut self,
+        field: &'a str,
+        path: &str,
+        value: V,
+        flag: bool,
+    ) -> Self
+    where
+        V: Into<SqlValue> + Clone,
+    {
+        if flag {
+            self = self.json_set_field(field, path, value);
+        }
+        self
+    }
+
+    pub fn json_set_field_opt<V>(mut self, field: &'a str, path: &str, value: Option<V>) -> Self
+    where
+        V: Into<SqlValue> + Clone,
+    {
+        if let Some(value) = value {
+            self = self.json_set_field(field, path, value);
+        }
+        self
+    }
+
+    pub fn json_set_field_opt_flag<V>(
+        mut self,
+        field: &'a str,
+        path: &str,
+        value: Option<V>,
+        flag: bool,
+    ) -> Self
+    where
+        V: Into<SqlValue> + Clone,
+    {
+        if flag {
+            self = self.json_set_field_opt(field, path, value);
+        }
+        self
+    }
+
+    /// `field = JSON_REMOVE(field, path)`
+    pub fn json_remove<F>(self, field_func: F, path: &str) -> Self
+    where
+        F: IntoField<'a, E>,
+    {
+        self.json_remove_field(field_func.into_field(), path)
+    }
+
+    pub fn json_remove_flag<F>(mut self, field_func: F, path: &str, flag: bool) -> Self
+    where
+        F: IntoField<'a, E>,
+    {
+        if flag {
+            self = self.json_remove(field_func, path);
+        }
+        self
+    }
+
+    pub fn json_remove_field(self, field: &'a str, path: &str) -> Self {
+        let json = Expr::func("JSON_REMOVE", vec![Expr::raw(field), Expr::value(path)]);
+        self.set_expr_field(field, json)
+    }
+
+    pub fn json_remove_field_flag(mut self, field: &'a str, path: &str, flag: bool) -> Self {
+        if flag {
+            self = self.json_remove_field(field, path);
+        }
+        self
+    }
+
+    /// `field = field + ?`
+    pub fn incr<F, V>(self, field_func: F, value: V) -> Self
+    where
+        F: IntoField<'a, E>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
+    {
+        self.incr_field(field_func.into_field(), value)
+    }
+
+    pub fn incr_flag<F, V>(mut self, field_func: F, value: V, flag: bool) -> Self
+    where
+        F: IntoField<'a, E>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
+    {
+        if flag {
+            self = self.incr(field_func, value);
+        }
+        self
+    }
+
+    pub fn incr_opt<F, V>(mut self, field_func: F, value: Option<V>) -> Self
+    where
+        F: IntoField<'a, E>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
+    {
+        if let Some(value) = value {
+            self = self.incr_field(field_func.into_field(), value);
+        }
+        self
+    }
+
+    pub fn incr_opt_flag<F, V>(mut self, field_func: F, value: Option<V>, flag: bool) -> Self
+    where
+        F: IntoField<'a, E>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
+    {
+        if flag {
+            self = self.incr_opt(field_func, value);
+        }
+        self
+    }
+
+    pub fn incr_field<V>(self, field: &'a str, value: V) -> Self
+    where
+        V: Into<SqlValue> + Clone,
+    {
+        self.set_expr_field(field, Expr::raw(field) + value)
+    }
+
+    pub fn incr_field_flag<V>(mut self, field: &'a str, value: V, flag: bool) -> Self
+    where
+        V: Into<SqlValue> + Clone,
+    {
+        if flag {
+            self = self.incr_field(field, value);
+        }
+        self
+    }
+
+    pub fn incr_field_opt<V>(mut self, field: &'a str, value: Option<V>) -> Self
+    where
+        V: Into<SqlValue> + Clone,
+    {
+        if let Some(value) = value {
+            self = self.incr_field(field, value);
+        }
+        self
+    }
+
+    pub fn incr_field_opt_flag<V>(mut self, field: &'a str, value: Option<V>, flag: bool) -> Self
+    where
+        V: Into<SqlValue> + Clone,
+    {
+        if flag {
+            self = self.incr_field_opt(field, value);
+        }
+        self
+    }
+
+    /// `field = field - ?`
+    pub fn decr<F, V>(self, field_func: F, value: V) -> Self
+    where
+        F: IntoField<'a, E>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
+    {
+        self.decr_field(field_func.into_field(), value)
+    }
+
+    pub fn decr_flag<F, V>(mut self, field_func: F, value: V, flag: bool) -> Self
+    where
+        F: IntoField<'a, E>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
+    {
+        if flag {
+            self = self.decr(field_func, value);
+        }
+        self
+    }
+
+    pub fn decr_opt<F, V>(mut self, field_func: F, value: Option<V>) -> Self
+    where
+        F: IntoField<'a, E>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
+    {
+        if let Some(value) = value {
+            self = self.decr_field(field_func.into_field(), value);
+        }
+        self
+    }
+
+    pub fn decr_opt_flag<F, V>(mut self, field_func: F, value: Option<V>, flag: bool) -> Self
+    where
+        F: IntoField<'a, E>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
+    {
+        if flag {
+            self = self.decr_opt(field_func, value);
+        }
+        self
+    }
+
+    pub fn decr_field<V>(self, field: &'a str, value: V) -> Self
+    where
+        V: Into<SqlValue> + Clone,
+    {
+        self.set_expr_field(field, Expr::raw(field) - value)
+    }
+
+    pub fn decr_field_flag<V>(mut self, field: &'a str, value: V, flag: bool) -> Self
+    where
+        V: Into<SqlValue> + Clone,
+    {
+        if flag {
+            self = self.decr_field(field, value);
+        }
+        self
+    }
+
+    pub fn decr_field_opt<V>(mut self, field: &'a str, value: Option<V>) -> Self
+    where
+        V: Into<SqlValue> + Clone,
+    {
+        if let Some(value) = value {
+            self = self.decr_field(field, value);
+        }
+        self
+    }
+
+    pub fn decr_field_opt_flag<V>(mut self, field: &'a str, value: Option<V>, flag: bool) -> Self
+    where
+        V: Into<SqlValue> + Clone,
+    {
+        if flag {
+            self = self.decr_field_opt(field, value);
+        }
+        self
+    }
+
+    /// `field = NULL`
+    pub fn set_null<F>(self, field_func: F) -> Self
+    where
+        F: IntoField<'a, E>,
+    {
+        self.set_null_field(field_func.into_field())
+    }
+
+    pub fn set_null_flag<F>(mut self, field_func: F, flag: bool) -> Self
+    where
+        F: IntoField<'a, E>,
+    {
+        if flag {
+            self = self.set_null(field_func);
+        }
+        self
+    }
+
+    pub fn set_null_field(self, field: &'a str) -> Self {
+        self.set_expr_field(field, Expr::raw("NULL"))
+    }
+
+    pub fn set_null_field_flag(mut self, field: &'a str, flag: bool) -> Self {
+        if flag {
+            self = self.set_null_field(field);
+        }
+        self
+    }
+
+    /// `field = other_field`
+    pub fn set_column<F, O>(self, field_func: F, other_field_func: O) -> Self
+    where
+        F: IntoField<'a, E>,
+        O: IntoField<'a, E>,
+    {
+        self.set_column_field(field_func.into_field(), other_field_func.into_field())
+    }
+
+    pub fn set_column_flag<F, O>(mut self, field_func: F, other_field_func: O, flag: bool) -> Self
+    where
+        F: IntoField<'a, E>,
+        O: IntoField<'a, E>,
+    {
+        if flag {
+            self = self.set_column(field_func, other_field_func);
+        }
+        self
+    }
+
+    pub fn set_column_field(self, field: &'a str, other_field: &'a str) -> Self {
+        self.set_expr_field(field, Expr::raw(other_field))
+    }
+
+    pub fn set_column_field_flag(
+        mut self,
+        field: &'a str,
+        other_field: &'a str,
+        flag: bool,
+    ) -> Self {
+        if flag {
+            self = self.set_column_field(field, other_field);
+        }
+        self
+    }
+
+    /// `field = expr`, e.g. `set_expr(User::update_time_field, Expr::now())`.
+    pub fn set_expr<F>(self, field_func: F, expr: Expr) -> Self
+    where
+        F: IntoField<'a, E>,
+    {
+        self.set_expr_field(field_func.into_field(), expr)
+    }
+
+    pub fn set_expr_flag<F>(mut self, field_func: F, expr: Expr, flag: bool) -> Self
+    where
+        F: IntoField<'a, E>,
+    {
+        if flag {
+            self = self.set_expr(field_func, expr);
+        }
+        self
+    }
+
+    pub fn set_expr_opt<F>(mut self, field_func: F, expr: Option<Expr>) -> Self
+    where
+        F: IntoField<'a, E>,
     {
-        if let Some(value) = value {
-            self = self.set_field(*field_func(), value);
+        if let Some(expr) = expr {
+            self = self.set_expr(field_func, expr);
         }
         self
     }
 
-    pub fn set_opt_flag<F, V>(mut self, field_func: F, value: Option<V>, flag: bool) -> Self
+    pub fn set_expr_opt_flag<F>(mut self, field_func: F, expr: Option<Expr>, flag: bool) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, E>,
     {
         if flag {
-            self = self.set_opt(field_func, value);
+            self = self.set_expr_opt(field_func, expr);
         }
         self
     }
 
-    pub fn set_field<V>(mut self, field: &'a str, value: V) -> Self
-    where
-        V: Into<SqlValue> + Clone,
-    {
+    /// sets the fields of `entity` chosen by their update strategy, `strategy` for fields without one.
+    ///
+    /// primary keys and skipped fields are never set.
+    pub fn set_entity(mut self, entity: &E, strategy: UpdateStrategy) -> Self {
+        for (column, value) in E::columns().iter().zip(entity.values()) {
+            if column.primary_key || column.skip {
+                continue;
+            }
+            if column
+                .update_strategy
+                .unwrap_or(strategy)
+                .should_set(&value)
+            {
+                self = self.set_field(column.name, value);
+            }
+        }
+        self
+    }
+
+    pub fn set_expr_field(mut self, field: &'a str, expr: Expr) -> Self {
         self.set_key.push(field);
-        self.set_value.push(value.into());
+        self.set_value.push(expr);
         self
     }
 
-    pub fn set_field_flag<V>(mut self, field: &'a str, value: V, flag: bool) -> Self
-    where
-        V: Into<SqlValue> + Clone,
-    {
+    pub fn set_expr_field_flag(mut self, field: &'a str, expr: Expr, flag: bool) -> Self {
         if flag {
-            self = self.set_field(field, value);
+            self = self.set_expr_field(field, expr);
         }
         self
     }
 
-    pub fn set_field_opt<V>(mut self, field: &'a str, value: Option<V>) -> Self
-    where
-        V: Into<SqlValue> + Clone,
-    {
-        if let Some(value) = value {
-            self = self.set_field(field, value);
+    pub fn set_expr_field_opt(mut self, field: &'a str, expr: Option<Expr>) -> Self {
+        if let Some(expr) = expr {
+            self = self.set_expr_field(field, expr);
         }
         self
     }
 
-    pub fn set_field_opt_flag<V>(mut self, field: &'a str, value: Option<V>, flag: bool) -> Self
-    where
-        V: Into<SqlValue> + Clone,
-    {
+    pub fn set_expr_field_opt_flag(
+        mut self,
+        field: &'a str,
+        expr: Option<Expr>,
+        flag: bool,
+    ) -> Self {
         if flag {
-            self = self.set_field_opt(field, value);
+            self = self.set_expr_field_opt(field, expr);
         }
         self
     }
 
     pub fn sql(&mut self) -> String {
         let mut sql = String::new();
-        if let Some(first) = self.first {
-            sql += first;
+        if let Some(first) = &self.first {
+            sql += first.sql;
             sql += " ";
         }
-        sql += &format!("UPDATE {} SET ", E::table_name());
+        sql += &format!(
+            "UPDATE {}{}{} SET ",
+            self.hints.optimizer_sql(),
+            E::table_name(),
+            self.hints.index_sql()
+        );
         sql += self
             .set_key
             .iter()
-            .map(|key| format!("{key} = ?"))
+            .zip(&self.set_value)
+            .map(|(key, value)| format!("{key} = {value}"))
             .collect::<Vec<_>>()
             .join(", ")
             .as_str();
         sql += self.r#where().as_str();
-        if let Some(last) = self.last {
-            sql += &format!(" {last}");
+        sql += &order_limit_sql(&self.order, self.limit, None);
+        if let Some(last) = &self.last {
+            sql += &format!(" {}", last.sql);
         }
         if let Some(comment) = self.comment {
-            sql += &format!(" -- {comment}");
+            sql += &comment_sql(comment);
         }
         sql
     }
 
+    fn values(&self) -> Vec<SqlValue> {
+        let mut values = vec![];
+        if let Some(first) = &self.first {
+            values.extend(first.values.iter().cloned());
+        }
+        for value in &self.set_value {
+            values.extend(value.values().iter().cloned());
+        }
+        for r#where in &self.wheres {
+            values.extend(r#where.values().iter().cloned());
+        }
+        values.extend(limit_values(self.limit, None));
+        if let Some(last) = &self.last {
+            values.extend(last.values.iter().cloned());
+        }
+        values
+    }
+
+    /// the statement, or one per chunk of an `IN` over `BIND_CHUNK_SIZE` values when no LIMIT applies.
+    fn statements(&mut self) -> Vec<Statement> {
+        let split = match self.limit {
+            Some(_) => None,
+            None => split_in(&self.wheres, &self.or_index, &self.bracket),
+        };
+        let Some((index, chunks)) = split else {
+            return vec![(self.sql(), self.values())];
+        };
+        let values = self.wheres[index].replace_values(vec![]);
+        let statements = chunks
+            .into_iter()
+            .map(|chunk| {
+                self.wheres[index].replace_values(chunk);
+                (self.sql(), self.values())
+            })
+            .collect();
+        self.wheres[index].replace_values(values);
+        statements
+    }
+
+    /// errors with `InvalidArgument` when nothing is set, `UPDATE t SET WHERE ..` is invalid sql.
     pub async fn execute(mut self) -> Result<u64> {
-        let sql = self.sql();
-        let values = self
-            .set_value
-            .iter()
-            .map(|value| value.clone())
-            .collect::<Vec<_>>();
-        Ok(self
-            .bind_query(bind_query(sqlx::query(&sql), &values))
-            .execute(self.db)
-            .await?
-            .rows_affected())
+        if self.set_key.is_empty() {
+            return Err(Error::InvalidArgument(format!(
+                "update of {} has no SET entries, call set/incr/set_expr first",
+                E::table_name()
+            )));
+        }
+        check_invalid(invalid_where(&self.wheres))?;
+        let mut statements = self.statements();
+        if statements.len() > 1 {
+            return execute_statements(&mut self.db, statements).await;
+        }
+        let (sql, values) = statements.remove(0);
+        Ok(execute!(
+            self.db,
+            sqlx::query_with(&sql, arguments(&values)?),
+            execute
+        )?
+        .rows_affected())
     }
 }
 
 impl<'a, 'd, E: Entity + for<'r> FromRow<'r, MySqlRow> + Send + Unpin> Wrapper<'a>
     for UpdateWrapper<'a, 'd, E>
 {
+    type Entity = E;
+
     fn wheres(&self) -> &Vec<Where<'a>> {
         &self.wheres
     }
@@ -505,19 +2045,21 @@ impl<'a, 'd, E: Entity + for<'r> FromRow<'r, MySqlRow> + Send + Unpin> Wrapper<'
         &mut self.bracket
     }
 
-    fn first(mut self, sql: &'a str) -> Self
+    fn first_values<V>(mut self, sql: &'a str, values: Vec<V>) -> Self
     where
+        V: Into<SqlValue> + Clone,
         Self: Sized,
     {
-        self.first = Some(sql);
+        self.first = Some(Clause::new(sql, values));
         self
     }
 
-    fn last(mut self, sql: &'a str) -> Self
+    fn last_values<V>(mut self, sql: &'a str, values: Vec<V>) -> Self
     where
+        V: Into<SqlValue> + Clone,
         Self: Sized,
     {
-        self.last = Some(sql);
+        self.last = Some(Clause::new(sql, values));
         self
     }
 
@@ -534,60 +2076,208 @@ pub struct DeleteWrapper<'a, 'd, E>
 where
     E: Entity + for<'r> FromRow<'r, MySqlRow> + Send + Unpin,
 {
+    hints: Hints<'a>,
     wheres: Vec<Where<'a>>,
     or_index: HashSet<usize>,
     bracket: Bracket,
-    first: Option<&'a str>,
-    last: Option<&'a str>,
+    order: Vec<Order<'a>>,
+    limit: Option<u64>,
+    first: Option<Clause<'a>>,
+    last: Option<Clause<'a>>,
     comment: Option<&'a str>,
-    db: &'d MySqlPool,
+    db: Executor<'d>,
     _ignore: PhantomData<E>,
 }
 
 impl<'a, 'd, E: Entity + for<'r> FromRow<'r, MySqlRow> + Send + Unpin> DeleteWrapper<'a, 'd, E> {
     pub fn new(db: &'d MySqlPool) -> Self {
         Self {
+            hints: Hints::default(),
             wheres: vec![],
             or_index: HashSet::new(),
             bracket: Bracket::new(),
+            order: vec![],
+            limit: None,
             first: None,
             last: None,
             comment: None,
-            db,
+            db: Executor::Pool(db),
             _ignore: Default::default(),
         }
     }
 
+    /// run on the connection of `tx` instead of the pool.
+    pub fn transaction(mut self, tx: &'d mut Transaction<'_, MySql>) -> Self {
+        self.db = Executor::Transaction(tx);
+        self
+    }
+
+    pub fn order_asc<F>(self, field_func: F) -> Self
+    where
+        F: IntoField<'a, E>,
+    {
+        self.order_asc_field(field_func.into_field())
+    }
+
+    pub fn order_desc<F>(self, field_func: F) -> Self
+    where
+        F: IntoField<'a, E>,
+    {
+        self.order_desc_field(field_func.into_field())
+    }
+
+    pub fn order_asc_field(mut self, field: &'a str) -> Self {
+        self.order.push(Order::new(field, true));
+        self
+    }
+
+    pub fn order_desc_field(mut self, field: &'a str) -> Self {
+        self.order.push(Order::new(field, false));
+        self
+    }
+
+    /// `LIMIT ?`, at most `limit` rows, in `order` when given.
+    pub fn limit(mut self, limit: u64) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// rendered as the `INDEX(table a, b)` optimizer hint (MySQL 8.0.20+), single-table DELETE has no
+    /// index hints and no `USE INDEX` counterpart.
+    pub fn force_index(mut self, indexes: &'a [&'a str]) -> Self {
+        self.hints.push_index(IndexHint::Force, indexes);
+        self
+    }
+
+    /// rendered as the `NO_INDEX(table a, b)` optimizer hint (MySQL 8.0.20+).
+    pub fn ignore_index(mut self, indexes: &'a [&'a str]) -> Self {
+        self.hints.push_index(IndexHint::Ignore, indexes);
+        self
+    }
+
+    /// MySQL 8 optimizer hint in `/*+ .. */` after the statement keyword, e.g. `BKA(user)`.
+    pub fn hint(mut self, hint: &'a str) -> Self {
+        self.hints.push_optimizer(hint);
+        self
+    }
+
     pub fn sql(&mut self) -> String {
         let mut sql = String::new();
-        if let Some(first) = self.first {
-            sql += first;
+        if let Some(first) = &self.first {
+            sql += first.sql;
             sql += " ";
         }
-        sql += &format!("DELETE FROM {}", E::table_name());
+        sql += &format!(
+            "DELETE {}FROM {}",
+            self.hints.delete_optimizer_sql(E::table_name()),
+            E::table_name()
+        );
         sql += self.r#where().as_str();
-        if let Some(last) = self.last {
-            sql += &format!(" {last}");
+        sql += &order_limit_sql(&self.order, self.limit, None);
+        if let Some(last) = &self.last {
+            sql += &format!(" {}", last.sql);
         }
         if let Some(comment) = self.comment {
-            sql += &format!(" -- {comment}");
+            sql += &comment_sql(comment);
         }
         sql
     }
 
+    fn values(&self) -> Vec<SqlValue> {
+        let mut values = vec![];
+        if let Some(first) = &self.first {
+            values.extend(first.values.iter().cloned());
+        }
+        for r#where in &self.wheres {
+            values.extend(r#where.values().iter().cloned());
+        }
+        values.extend(limit_values(self.limit, None));
+        if let Some(last) = &self.last {
+            values.extend(last.values.iter().cloned());
+        }
+        values
+    }
+
+    /// the statement, or one per chunk of an `IN` over `BIND_CHUNK_SIZE` values when no LIMIT applies.
+    fn statements(&mut self) -> Vec<Statement> {
+        let split = match self.limit {
+            Some(_) => None,
+            None => split_in(&self.wheres, &self.or_index, &self.bracket),
+        };
+        let Some((index, chunks)) = split else {
+            return vec![(self.sql(), self.values())];
+        };
+        let values = self.wheres[index].replace_values(vec![]);
+        let statements = chunks
+            .into_iter()
+            .map(|chunk| {
+                self.wheres[index].replace_values(chunk);
+                (self.sql(), self.values())
+            })
+            .collect();
+        self.wheres[index].replace_values(values);
+        statements
+    }
+
     pub async fn execute(mut self) -> Result<u64> {
-        let sql = self.sql();
-        Ok(self
-            .bind_query(sqlx::query(&sql))
-            .execute(self.db)
-            .await?
-            .rows_affected())
+        self.execute_once().await
+    }
+
+    /// deletes `batch_size` rows at a time until a batch deletes nothing, sleeping `pause` in between
+    /// to spare the replicas, returns the total rows.
+    ///
+    /// add `order_asc` on the primary key to purge the oldest rows first.
+    pub async fn delete_in_batches(self, batch_size: u64, pause: Duration) -> Result<u64> {
+        self.delete_in_batches_with_progress(batch_size, pause, |_, _| {})
+            .await
+    }
+
+    /// `delete_in_batches` calling `progress(batch_rows, total_rows)` after every batch.
+    pub async fn delete_in_batches_with_progress<P>(
+        mut self,
+        batch_size: u64,
+        pause: Duration,
+        mut progress: P,
+    ) -> Result<u64>
+    where
+        P: FnMut(u64, u64),
+    {
+        self.limit = Some(batch_size);
+        let mut total = 0;
+        loop {
+            let rows = self.execute_once().await?;
+            if rows == 0 {
+                return Ok(total);
+            }
+            total += rows;
+            progress(rows, total);
+            if !pause.is_zero() {
+                tokio::time::sleep(pause).await;
+            }
+        }
+    }
+
+    async fn execute_once(&mut self) -> Result<u64> {
+        check_invalid(invalid_where(&self.wheres))?;
+        let mut statements = self.statements();
+        if statements.len() > 1 {
+            return execute_statements(&mut self.db, statements).await;
+        }
+        let (sql, values) = statements.remove(0);
+        Ok(execute!(
+            self.db,
+            sqlx::query_with(&sql, arguments(&values)?),
+            execute
+        )?
+        .rows_affected())
     }
 }
 
 impl<'a, 'd, E: Entity + for<'r> FromRow<'r, MySqlRow> + Send + Unpin> Wrapper<'a>
     for DeleteWrapper<'a, 'd, E>
 {
+    type Entity = E;
+
     fn wheres(&self) -> &Vec<Where<'a>> {
         &self.wheres
     }
@@ -612,19 +2302,21 @@ impl<'a, 'd, E: Entity + for<'r> FromRow<'r, MySqlRow> + Send + Unpin> Wrapper<'
         &mut self.bracket
     }
 
-    fn first(mut self, sql: &'a str) -> Self
+    fn first_values<V>(mut self, sql: &'a str, values: Vec<V>) -> Self
     where
+        V: Into<SqlValue> + Clone,
         Self: Sized,
     {
-        self.first = Some(sql);
+        self.first = Some(Clause::new(sql, values));
         self
     }
 
-    fn last(mut self, sql: &'a str) -> Self
+    fn last_values<V>(mut self, sql: &'a str, values: Vec<V>) -> Self
     where
+        V: Into<SqlValue> + Clone,
         Self: Sized,
     {
-        self.last = Some(sql);
+        self.last = Some(Clause::new(sql, values));
         self
     }
 
@@ -636,3 +2328,886 @@ impl<'a, 'd, E: Entity + for<'r> FromRow<'r, MySqlRow> + Send + Unpin> Wrapper<'
         self
     }
 }
+
+/// `INSERT [IGNORE] INTO` / `REPLACE INTO` built from lambda fields, or `INSERT INTO .. SELECT`.
+pub struct InsertWrapper<'a, 'd, E>
+where
+    E: Entity + for<'r> FromRow<'r, MySqlRow> + Send + Unpin,
+{
+    mode: InsertMode,
+    set_key: Vec<&'a str>,
+    set_value: Vec<Expr>,
+    source: Option<(String, Vec<SqlValue>)>,
+    comment: Option<&'a str>,
+    /// error of the `insert_select` query or of `set` combined with it, reported when the statement runs.
+    invalid: Option<String>,
+    db: Executor<'d>,
+    _ignore: PhantomData<E>,
+}
+
+#[derive(Clone, Copy)]
+enum InsertMode {
+    Insert,
+    Ignore,
+    Replace,
+}
+
+impl<'a, 'd, E: Entity + for<'r> FromRow<'r, MySqlRow> + Send + Unpin> InsertWrapper<'a, 'd, E> {
+    pub fn new(db: &'d MySqlPool) -> Self {
+        Self {
+            mode: InsertMode::Insert,
+            set_key: Vec::new(),
+            set_value: Vec::new(),
+            source: None,
+            comment: None,
+            invalid: None,
+            db: Executor::Pool(db),
+            _ignore: Default::default(),
+        }
+    }
+
+    /// run on the connection of `tx` instead of the pool.
+    pub fn transaction(mut self, tx: &'d mut Transaction<'_, MySql>) -> Self {
+        self.db = Executor::Transaction(tx);
+        self
+    }
+
+    /// `INSERT IGNORE`, rows conflicting with a unique key are skipped.
+    pub fn ignore(mut self) -> Self {
+        self.mode = InsertMode::Ignore;
+        self
+    }
+
+    /// `REPLACE INTO`, rows conflicting with a unique key are deleted before the insert.
+    pub fn replace(mut self) -> Self {
+        self.mode = InsertMode::Replace;
+        self
+    }
+
+    pub fn set<F, V>(self, field_func: F, value: V) -> Self
+    where
+        F: IntoField<'a, E>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
+    {
+        self.set_field(field_func.into_field(), value)
+    }
+
+    pub fn set_flag<F, V>(mut self, field_func: F, value: V, flag: bool) -> Self
+    where
+        F: IntoField<'a, E>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
+    {
+        if flag {
+            self = self.set(field_func, value);
+        }
+        self
+    }
+
+    pub fn set_opt<F, V>(mut self, field_func: F, value: Option<V>) -> Self
+    where
+        F: IntoField<'a, E>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
+    {
+        if let Some(value) = value {
+            self = self.set_field(field_func.into_field(), value);
+        }
+        self
+    }
+
+    pub fn set_opt_flag<F, V>(mut self, field_func: F, value: Option<V>, flag: bool) -> Self
+    where
+        F: IntoField<'a, E>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
+    {
+        if flag {
+            self = self.set_opt(field_func, value);
+        }
+        self
+    }
+
+    pub fn set_field<V>(self, field: &'a str, value: V) -> Self
+    where
+        V: Into<SqlValue> + Clone,
+    {
+        self.set_expr_field(field, Expr::value(value))
+    }
+
+    pub fn set_field_flag<V>(mut self, field: &'a str, value: V, flag: bool) -> Self
+    where
+        V: Into<SqlValue> + Clone,
+    {
+        if flag {
+            self = self.set_field(field, value);
+        }
+        self
+    }
+
+    pub fn set_field_opt<V>(mut self, field: &'a str, value: Option<V>) -> Self
+    where
+        V: Into<SqlValue> + Clone,
+    {
+        if let Some(value) = value {
+            self = self.set_field(field, value);
+        }
+        self
+    }
+
+    pub fn set_field_opt_flag<V>(mut self, field: &'a str, value: Option<V>, flag: bool) -> Self
+    where
+        V: Into<SqlValue> + Clone,
+    {
+        if flag {
+            self = self.set_field_opt(field, value);
+        }
+        self
+    }
+
+    /// `field = expr`, e.g. `set_expr(User::create_time_field, Expr::now())`.
+    pub fn set_expr<F>(self, field_func: F, expr: Expr) -> Self
+    where
+        F: IntoField<'a, E>,
+    {
+        self.set_expr_field(field_func.into_field(), expr)
+    }
+
+    pub fn set_expr_field(mut self, field: &'a str, expr: Expr) -> Self {
+        if self.source.is_some() {
+            self.set_with_insert_select();
+            return self;
+        }
+        self.set_key.push(field);
+        self.set_value.push(expr);
+        self
+    }
+
+    /// `set` values have no place in `INSERT .. SELECT`, fails the statement instead of dropping them.
+    fn set_with_insert_select(&mut self) {
+        self.invalid.get_or_insert_with(|| {
+            format!(
+                "insert into {} combines set with insert_select, select the values in the query instead",
+                E::table_name()
+            )
+        });
+    }
+
+    /// `INSERT INTO t (target_fields) SELECT ..`, copies the rows of `query` on the server.
+    ///
+    /// the selected columns of `query` must match `target_fields` in order, combined with `set` the
+    /// statement fails with `InvalidArgument`.
+    pub fn insert_select<F, S>(self, target_fields: F, query: QueryWrapper<'a, '_, S>) -> Self
+    where
+        F: IntoFields<'a, E>,
+        S: Entity + for<'r> FromRow<'r, MySqlRow> + Send + Unpin,
+    {
+        self.insert_select_field(target_fields.into_fields(), query)
+    }
+
+    pub fn insert_select_field<S>(
+        mut self,
+        target_fields: Vec<&'a str>,
+        mut query: QueryWrapper<'a, '_, S>,
+    ) -> Self
+    where
+        S: Entity + for<'r> FromRow<'r, MySqlRow> + Send + Unpin,
+    {
+        self.invalid = query.invalid().map(str::to_string);
+        if !self.set_value.is_empty() {
+            self.set_with_insert_select();
+        }
+        self.set_key = target_fields;
+        self.source = Some((query.sql(), query.values()));
+        self
+    }
+
+    pub fn comment(mut self, comment: &'a str) -> Self {
+        self.comment = Some(comment);
+        self
+    }
+
+    pub fn sql(&self) -> String {
+        let mut sql = match self.mode {
+            InsertMode::Insert => "INSERT INTO ",
+            InsertMode::Ignore => "INSERT IGNORE INTO ",
+            InsertMode::Replace => "REPLACE INTO ",
+        }
+        .to_string();
+        sql += &format!("{} ({})", E::table_name(), self.set_key.join(", "));
+        match &self.source {
+            Some((source_sql, _)) => sql += &format!(" {source_sql}"),
+            None => {
+                let values = self
+                    .set_value
+                    .iter()
+                    .map(|value| value.sql())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                sql += &format!(" VALUES ({values})");
+            }
+        }
+        if let Some(comment) = self.comment {
+            sql += &comment_sql(comment);
+        }
+        sql
+    }
+
+    fn values(&self) -> Vec<SqlValue> {
+        match &self.source {
+            Some((_, values)) => values.clone(),
+            None => self
+                .set_value
+                .iter()
+                .flat_map(|value| value.values().iter().cloned())
+                .collect(),
+        }
+    }
+
+    /// the `AUTO_INCREMENT` id of the inserted row, 0 when `ignore` skipped it or the table has none.
+    pub async fn execute(mut self) -> Result<u64> {
+        Ok(self.execute_result().await?.last_insert_id())
+    }
+
+    /// affected rows, e.g. the copied rows of `insert_select`, `REPLACE` counts a replaced row twice.
+    pub async fn execute_rows(mut self) -> Result<u64> {
+        Ok(self.execute_result().await?.rows_affected())
+    }
+
+    async fn execute_result(&mut self) -> Result<MySqlQueryResult> {
+        let sql = self.sql();
+        check_invalid(self.invalid.as_deref())?;
+        execute!(
+            self.db,
+            sqlx::query_with(&sql, arguments(&self.values())?),
+            execute
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::BIND_CHUNK_SIZE;
+    use crate::tests::{User, UserRole, pool};
+
+    fn values(values: Vec<SqlValue>) -> String {
+        format!("{values:?}")
+    }
+
+    #[tokio::test]
+    async fn group_by_having() {
+        let pool = pool();
+        let mut query = QueryWrapper::<User>::new(&pool)
+            .select_field(&["username", "COUNT(*) AS total"])
+            .eq(User::age_field, 18)
+            .group_by(User::username_field)
+            .having_values("COUNT(*) > ?", vec![1]);
+        assert_eq!(
+            query.sql(),
+            "SELECT username, COUNT(*) AS total FROM user WHERE age = ? GROUP BY username HAVING COUNT(*) > ?"
+        );
+        assert_eq!(values(query.values()), "[I32(18), I32(1)]");
+    }
+
+    #[tokio::test]
+    async fn having_replaces_values() {
+        let pool = pool();
+        let mut query = QueryWrapper::<User>::new(&pool)
+            .group_by(User::username_field)
+            .having_values("COUNT(*) > ?", vec![1])
+            .having("COUNT(*) > 2");
+        assert_eq!(
+            query.sql(),
+            "SELECT * FROM user GROUP BY username HAVING COUNT(*) > 2"
+        );
+        assert_eq!(values(query.values()), "[]");
+    }
+
+    #[tokio::test]
+    async fn apply_and_clauses_bind_in_order() {
+        let pool = pool();
+        let mut query = QueryWrapper::<User>::new(&pool)
+            .first_values(
+                "WITH minors AS (SELECT ID FROM user WHERE age < ?)",
+                vec![16],
+            )
+            .eq(User::age_field, 18)
+            .apply("DATE(create_time) = ?", vec!["2025-01-01"])
+            .last_values("LIMIT ?", vec![10]);
+        assert_eq!(
+            query.sql(),
+            "WITH minors AS (SELECT ID FROM user WHERE age < ?) SELECT * FROM user WHERE age = ? AND (DATE(create_time) = ?) LIMIT ?"
+        );
+        assert_eq!(
+            values(query.values()),
+            r#"[I32(16), I32(18), Str("2025-01-01"), I32(10)]"#
+        );
+    }
+
+    #[tokio::test]
+    async fn comment_is_escaped() {
+        let pool = pool();
+        let mut delete = DeleteWrapper::<User>::new(&pool)
+            .eq(User::id_field, 1u64)
+            .comment("a */ DROP TABLE user;\n-- b");
+        assert_eq!(
+            delete.sql(),
+            "DELETE FROM user WHERE ID = ? /* a * / DROP TABLE user; -- b */"
+        );
+    }
+
+    #[tokio::test]
+    async fn null_conditions() {
+        let pool = pool();
+        let mut query = QueryWrapper::<User>::new(&pool)
+            .eq(User::age_field, None::<i32>)
+            .ne(User::username_field, None::<String>)
+            .eq_null_safe(User::create_time_field, None::<String>)
+            .null(User::id_field)
+            .not_null(User::age_field);
+        assert_eq!(
+            query.sql(),
+            "SELECT * FROM user WHERE age IS NULL AND username IS NOT NULL AND create_time <=> ? AND ID IS NULL AND age IS NOT NULL"
+        );
+        assert_eq!(values(query.values()), "[Null]");
+    }
+
+    #[tokio::test]
+    async fn tuple_in() {
+        let pool = pool();
+        let mut query = QueryWrapper::<UserRole>::new(&pool).in_tuple(
+            (UserRole::user_id_field, UserRole::role_id_field),
+            vec![(1u64, 2u64), (1, 3)],
+        );
+        assert_eq!(
+            query.sql(),
+            "SELECT * FROM user_role WHERE (user_id, role_id) IN ((?, ?), (?, ?))"
+        );
+        assert_eq!(values(query.values()), "[U64(1), U64(2), U64(1), U64(3)]");
+        assert!(query.arguments().is_ok());
+    }
+
+    #[tokio::test]
+    async fn tuple_in_with_wrong_width_is_an_error() {
+        let pool = pool();
+        let query = QueryWrapper::<UserRole>::new(&pool)
+            .in_tuple_field(vec!["user_id", "role_id"], vec![1u64, 2]);
+        let err = query.arguments().unwrap_err();
+        assert!(matches!(err, Error::InvalidArgument(_)), "{err}");
+        let union = QueryWrapper::<User>::new(&pool).union(query);
+        assert!(matches!(
+            union.arguments(&union.values()),
+            Err(Error::InvalidArgument(_))
+        ));
+        let update = UpdateWrapper::<UserRole>::new(&pool)
+            .set(UserRole::role_id_field, 1u64)
+            .in_tuple_field(vec![], vec![1u64]);
+        assert!(matches!(
+            update.execute().await,
+            Err(Error::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn output_names() {
+        assert_eq!(output_name("ID"), "ID");
+        assert_eq!(output_name("u.`ID`"), "ID");
+        assert_eq!(output_name("u.ID AS uid"), "uid");
+        assert_eq!(output_name("COUNT(*) as total"), "total");
+        assert_eq!(output_name("u.*"), "*");
+        assert_eq!(output_name("LOWER(u.name)"), "LOWER(u.name)");
+        assert_eq!(output_name("CAST(u.ID AS CHAR)"), "CAST(u.ID AS CHAR)");
+        assert_eq!(output_name("CAST(u.ID AS CHAR) AS `uid`"), "uid");
+        assert_eq!(
+            output_name("CAST(u.ID AS DECIMAL(10, 2))"),
+            "CAST(u.ID AS DECIMAL(10, 2))"
+        );
+    }
+
+    #[tokio::test]
+    async fn map_by_needs_a_selected_key() {
+        let pool = pool();
+        let query = QueryWrapper::<User>::new(&pool);
+        assert!(query.selects("ID"));
+        assert!(!query.selects("id"));
+        assert!(!query.selects("ignore"));
+        let query = QueryWrapper::<User>::new(&pool).select_field(&["u.ID AS uid", "username"]);
+        assert!(query.selects("uid"));
+        assert!(!query.selects("ID"));
+        let query = QueryWrapper::<User>::new(&pool).select_field(&["CAST(ID AS CHAR)"]);
+        assert!(query.selects("CAST(ID AS CHAR)"));
+        assert!(!query.selects("CHAR)"));
+        let result = QueryWrapper::<User>::new(&pool)
+            .select_field(&["username"])
+            .map_by_field::<u64>("u.ID")
+            .await;
+        assert!(matches!(result, Err(Error::InvalidArgument(_))));
+    }
+
+    #[tokio::test]
+    async fn select_exclude_and_alias() {
+        let pool = pool();
+        let mut query = QueryWrapper::<User>::new(&pool)
+            .select_exclude(vec![User::create_time_field])
+            .select_as(User::username_field, "name");
+        assert_eq!(
+            query.sql(),
+            "SELECT ID, username, age, username AS name FROM user"
+        );
+    }
+
+    #[tokio::test]
+    async fn limit_and_offset() {
+        let pool = pool();
+        let mut query = QueryWrapper::<User>::new(&pool)
+            .eq(User::age_field, 18)
+            .order_desc(User::id_field)
+            .limit(10)
+            .offset(20);
+        assert_eq!(
+            query.sql(),
+            "SELECT * FROM user WHERE age = ? ORDER BY ID DESC LIMIT ? OFFSET ?"
+        );
+        assert_eq!(values(query.values()), "[I32(18), U64(10), U64(20)]");
+        let mut query = QueryWrapper::<User>::new(&pool).offset(5);
+        assert_eq!(
+            query.sql(),
+            "SELECT * FROM user LIMIT 18446744073709551615 OFFSET ?"
+        );
+        assert_eq!(values(query.values()), "[U64(5)]");
+    }
+
+    #[tokio::test]
+    async fn lock_rendering() {
+        let pool = pool();
+        let sql = |query: QueryWrapper<User>| query.eq(User::id_field, 1u64).sql();
+        let query = || QueryWrapper::<User>::new(&pool);
+        assert_eq!(
+            sql(query().for_update()),
+            "SELECT * FROM user WHERE ID = ? FOR UPDATE"
+        );
+        assert_eq!(
+            sql(query().for_share().nowait()),
+            "SELECT * FROM user WHERE ID = ? FOR SHARE NOWAIT"
+        );
+        assert_eq!(
+            sql(query().skip_locked()),
+            "SELECT * FROM user WHERE ID = ? FOR UPDATE SKIP LOCKED"
+        );
+        assert_eq!(
+            sql(query().lock_in_share_mode().skip_locked()),
+            "SELECT * FROM user WHERE ID = ? FOR SHARE SKIP LOCKED"
+        );
+        assert_eq!(
+            sql(query().limit(1).for_update()),
+            "SELECT * FROM user WHERE ID = ? LIMIT ? FOR UPDATE"
+        );
+    }
+
+    #[tokio::test]
+    async fn locks_need_a_transaction() {
+        let pool = pool();
+        let query = QueryWrapper::<User>::new(&pool).for_update();
+        assert!(matches!(query.arguments(), Err(Error::InvalidArgument(_))));
+        let union = QueryWrapper::<User>::new(&pool)
+            .union_all(QueryWrapper::<User>::new(&pool).lock_in_share_mode());
+        assert!(matches!(
+            union.arguments(&union.values()),
+            Err(Error::InvalidArgument(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn union_parts_are_parenthesized_when_needed() {
+        let pool = pool();
+        let union = QueryWrapper::<User>::new(&pool)
+            .eq(User::age_field, 18)
+            .union_all(
+                QueryWrapper::<User>::new(&pool)
+                    .order_desc(User::id_field)
+                    .limit(5),
+            )
+            .union(QueryWrapper::<User>::new(&pool).eq(User::age_field, 20))
+            .order_asc_field("ID")
+            .limit(10);
+        assert_eq!(
+            union.sql(),
+            "SELECT * FROM user WHERE age = ? UNION ALL (SELECT * FROM user ORDER BY ID DESC LIMIT ?) UNION SELECT * FROM user WHERE age = ? ORDER BY ID ASC LIMIT ?"
+        );
+        assert_eq!(
+            values(union.values()),
+            "[I32(18), U64(5), I32(20), U64(10)]"
+        );
+    }
+
+    #[tokio::test]
+    async fn union_parts_reject_first_and_last() {
+        let pool = pool();
+        let union = QueryWrapper::<User>::new(&pool)
+            .union(QueryWrapper::<User>::new(&pool).first("EXPLAIN"));
+        assert!(matches!(
+            union.arguments(&union.values()),
+            Err(Error::InvalidArgument(_))
+        ));
+        let union = QueryWrapper::<User>::new(&pool)
+            .last("LIMIT 1")
+            .union(QueryWrapper::<User>::new(&pool));
+        assert!(matches!(
+            union.arguments(&union.values()),
+            Err(Error::InvalidArgument(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn with_cte() {
+        let pool = pool();
+        let adults = QueryWrapper::<User>::new(&pool).ge(User::age_field, 18);
+        let mut query = QueryWrapper::<User>::new(&pool)
+            .with("adult", adults)
+            .with_raw(
+                "named",
+                "SELECT * FROM adult WHERE username = ?",
+                vec!["bob"],
+            )
+            .from("named")
+            .limit(1);
+        assert_eq!(
+            query.sql(),
+            "WITH adult AS (SELECT * FROM user WHERE age >= ?), named AS (SELECT * FROM adult WHERE username = ?) SELECT * FROM named LIMIT ?"
+        );
+        assert_eq!(values(query.values()), r#"[I32(18), Str("bob"), U64(1)]"#);
+    }
+
+    #[tokio::test]
+    async fn with_recursive_tree() {
+        let pool = pool();
+        let mut query = QueryWrapper::<User>::new(&pool)
+            .descendants(User::id_field, User::age_field, 1u64)
+            .eq(User::username_field, "bob");
+        assert_eq!(
+            query.sql(),
+            "WITH RECURSIVE user_tree AS (SELECT * FROM user WHERE age = ? UNION ALL SELECT t.* FROM user AS t JOIN user_tree ON t.age = user_tree.ID) SELECT * FROM user_tree WHERE username = ?"
+        );
+        assert_eq!(values(query.values()), r#"[U64(1), Str("bob")]"#);
+        let anchor = QueryWrapper::<User>::new(&pool).eq(User::id_field, 1u64);
+        let step = QueryWrapper::<User>::new(&pool).from("user AS t JOIN tree ON t.age = tree.ID");
+        let mut query = QueryWrapper::<User>::new(&pool)
+            .with_recursive("tree", anchor.union_all(step))
+            .from("tree");
+        assert_eq!(
+            query.sql(),
+            "WITH RECURSIVE tree AS (SELECT * FROM user WHERE ID = ? UNION ALL SELECT * FROM user AS t JOIN tree ON t.age = tree.ID) SELECT * FROM tree"
+        );
+    }
+
+    #[tokio::test]
+    async fn top_n_per_group_keeps_clauses_outside_the_ranked_rows() {
+        let pool = pool();
+        let mut query = QueryWrapper::<User>::new(&pool)
+            .first_values("/* ? */", vec![1])
+            .hint("BKA(user)")
+            .force_index(&["idx_age"])
+            .eq(User::username_field, "bob")
+            .limit(10)
+            .comment("top")
+            .top_n_per_group(User::age_field, OrderBy::desc(Expr::col(User::id_field)), 3);
+        assert_eq!(
+            query.sql(),
+            "/* ? */ WITH ranked AS (SELECT user.*, ROW_NUMBER() OVER (PARTITION BY age ORDER BY ID DESC) AS row_num FROM user FORCE INDEX (idx_age) WHERE username = ?) SELECT /*+ BKA(user) */ * FROM ranked WHERE row_num <= ? ORDER BY age ASC, row_num ASC LIMIT ? /* top */"
+        );
+        assert_eq!(
+            values(query.values()),
+            r#"[I32(1), Str("bob"), U64(3), U64(10)]"#
+        );
+    }
+
+    #[tokio::test]
+    async fn expression_conditions() {
+        let pool = pool();
+        let mut query = QueryWrapper::<User>::new(&pool)
+            .expr((Expr::col(User::age_field) + 1).gt(18))
+            .expr_flag(Expr::col(User::age_field).eq(0), false)
+            .expr_opt(None)
+            .expr(Expr::col(User::username_field).eq(None::<String>))
+            .eq_column(User::username_field, User::create_time_field)
+            .gt_column_field("age", "ID");
+        assert_eq!(
+            query.sql(),
+            "SELECT * FROM user WHERE ((age + ?) > ?) AND username IS NULL AND (username = create_time) AND (age > ID)"
+        );
+        assert_eq!(values(query.values()), "[I32(1), I32(18)]");
+    }
+
+    #[tokio::test]
+    async fn json_conditions_and_setters() {
+        let pool = pool();
+        let mut query = QueryWrapper::<User>::new(&pool)
+            .json_eq(User::username_field, "$.city", "Paris")
+            .json_eq(User::username_field, "$.zip", None::<String>)
+            .json_contains(User::username_field, serde_json::json!(["a"]))
+            .member_of_field("age", 18);
+        assert_eq!(
+            query.sql(),
+            "SELECT * FROM user WHERE (JSON_UNQUOTE(JSON_EXTRACT(username, ?)) = ?) AND JSON_UNQUOTE(JSON_EXTRACT(username, ?)) IS NULL AND JSON_CONTAINS(username, CAST(? AS JSON)) AND (? MEMBER OF(age))"
+        );
+        assert_eq!(
+            values(query.values()),
+            r#"[Str("$.city"), Str("Paris"), Str("$.zip"), Json(Array [String("a")]), I32(18)]"#
+        );
+
+        let mut update = UpdateWrapper::<User>::new(&pool)
+            .json_set(User::username_field, "$.tags", serde_json::json!(["a"]))
+            .json_remove_field("ignore", "$.old")
+            .eq(User::id_field, 1u64);
+        assert_eq!(
+            update.sql(),
+            "UPDATE user SET username = JSON_SET(username, ?, CAST(? AS JSON)), ignore = JSON_REMOVE(ignore, ?) WHERE ID = ?"
+        );
+        assert_eq!(
+            values(update.values()),
+            r#"[Str("$.tags"), Json(Array [String("a")]), Str("$.old"), U64(1)]"#
+        );
+    }
+
+    #[tokio::test]
+    async fn full_text_search() {
+        let pool = pool();
+        let mut query = QueryWrapper::<User>::new(&pool)
+            .select_relevance(
+                (User::username_field,),
+                "rust",
+                MatchMode::NaturalLanguage,
+                "score",
+            )
+            .match_against_field(vec!["username"], "rust", MatchMode::NaturalLanguage)
+            .eq(User::age_field, 18);
+        assert_eq!(
+            query.sql(),
+            "SELECT user.*, MATCH (username) AGAINST (? IN NATURAL LANGUAGE MODE) AS score FROM user WHERE MATCH (username) AGAINST (? IN NATURAL LANGUAGE MODE) AND age = ? ORDER BY score DESC"
+        );
+        assert_eq!(
+            values(query.values()),
+            r#"[Str("rust"), Str("rust"), I32(18)]"#
+        );
+    }
+
+    #[tokio::test]
+    async fn index_and_optimizer_hints() {
+        let pool = pool();
+        let mut query = QueryWrapper::<User>::new(&pool)
+            .force_index(&["idx_username"])
+            .ignore_index(&["idx_age"])
+            .hint("NO_ICP(user)")
+            .max_execution_time(Duration::from_secs(2))
+            .eq(User::username_field, "a");
+        assert_eq!(
+            query.sql(),
+            "SELECT /*+ NO_ICP(user) MAX_EXECUTION_TIME(2000) */ * FROM user FORCE INDEX (idx_username) IGNORE INDEX (idx_age) WHERE username = ?"
+        );
+
+        let mut update = UpdateWrapper::<User>::new(&pool)
+            .use_index(&["idx_age"])
+            .hint("BKA(user)")
+            .set(User::age_field, 1)
+            .eq(User::age_field, 0);
+        assert_eq!(
+            update.sql(),
+            "UPDATE /*+ BKA(user) */ user USE INDEX (idx_age) SET age = ? WHERE age = ?"
+        );
+
+        let mut delete = DeleteWrapper::<User>::new(&pool)
+            .force_index(&["idx_age"])
+            .ignore_index(&["idx_username"])
+            .eq(User::age_field, 0);
+        assert_eq!(
+            delete.sql(),
+            "DELETE /*+ INDEX(user idx_age) NO_INDEX(user idx_username) */ FROM user WHERE age = ?"
+        );
+    }
+
+    #[tokio::test]
+    async fn index_hints_skip_ctes() {
+        let pool = pool();
+        let adults = QueryWrapper::<User>::new(&pool).ge(User::age_field, 18);
+        let mut query = QueryWrapper::<User>::new(&pool)
+            .with("adults", adults)
+            .from("adults a")
+            .use_index(&["idx_age"]);
+        assert_eq!(
+            query.sql(),
+            "WITH adults AS (SELECT * FROM user WHERE age >= ?) SELECT * FROM adults a"
+        );
+        let mut query = QueryWrapper::<User>::new(&pool)
+            .from("user u")
+            .use_index(&["idx_age"]);
+        assert_eq!(query.sql(), "SELECT * FROM user u USE INDEX (idx_age)");
+    }
+
+    #[tokio::test]
+    async fn update_and_delete_order_and_limit() {
+        let pool = pool();
+        let mut update = UpdateWrapper::<User>::new(&pool)
+            .set(User::age_field, 1)
+            .eq(User::age_field, 0)
+            .order_asc(User::id_field)
+            .order_desc_field("create_time")
+            .limit(1000);
+        assert_eq!(
+            update.sql(),
+            "UPDATE user SET age = ? WHERE age = ? ORDER BY ID ASC, create_time DESC LIMIT ?"
+        );
+        assert_eq!(values(update.values()), "[I32(1), I32(0), U64(1000)]");
+
+        let mut delete = DeleteWrapper::<User>::new(&pool)
+            .lt(User::create_time_field, "2025-01-01")
+            .order_asc(User::id_field)
+            .limit(500)
+            .last("/* purge */");
+        assert_eq!(
+            delete.sql(),
+            "DELETE FROM user WHERE create_time < ? ORDER BY ID ASC LIMIT ? /* purge */"
+        );
+        assert_eq!(values(delete.values()), r#"[Str("2025-01-01"), U64(500)]"#);
+    }
+
+    #[tokio::test]
+    async fn update_setters() {
+        let pool = pool();
+        let mut update = UpdateWrapper::<User>::new(&pool)
+            .incr(User::age_field, 2)
+            .decr_field("ID", 1u64)
+            .set_null(User::username_field)
+            .set_column(User::create_time_field, User::username_field)
+            .set_expr_field("ignore", Expr::now())
+            .eq(User::id_field, 1u64);
+        assert_eq!(
+            update.sql(),
+            "UPDATE user SET age = (age + ?), ID = (ID - ?), username = NULL, create_time = username, ignore = NOW() WHERE ID = ?"
+        );
+        assert_eq!(values(update.values()), "[I32(2), U64(1), U64(1)]");
+    }
+
+    #[tokio::test]
+    async fn update_without_set_is_an_error() {
+        let pool = pool();
+        let result = UpdateWrapper::<User>::new(&pool)
+            .set_flag(User::age_field, 1, false)
+            .eq(User::id_field, 1u64)
+            .execute()
+            .await;
+        assert!(matches!(result, Err(Error::InvalidArgument(_))));
+    }
+
+    #[tokio::test]
+    async fn set_entity_follows_update_strategy() {
+        let pool = pool();
+        let user = User {
+            id: Some(1),
+            username: Some(String::new()),
+            create_time: Some("2025-01-01".to_string()),
+            ..Default::default()
+        };
+        let mut update = UpdateWrapper::<User>::new(&pool)
+            .set_entity(&user, UpdateStrategy::NotNull)
+            .eq(User::id_field, 1u64);
+        assert_eq!(update.sql(), "UPDATE user SET username = ? WHERE ID = ?");
+        let mut update = UpdateWrapper::<User>::new(&pool)
+            .set_entity(&user, UpdateStrategy::NotEmpty)
+            .set(User::age_field, 1);
+        assert_eq!(update.sql(), "UPDATE user SET age = ?");
+        let mut update =
+            UpdateWrapper::<User>::new(&pool).set_entity(&user, UpdateStrategy::Always);
+        assert_eq!(update.sql(), "UPDATE user SET username = ?, age = ?");
+        assert_eq!(values(update.values()), r#"[Str(""), Null]"#);
+    }
+
+    #[tokio::test]
+    async fn insert_modes() {
+        let pool = pool();
+        let insert = InsertWrapper::<User>::new(&pool)
+            .ignore()
+            .set(User::username_field, "a")
+            .set_expr(User::create_time_field, Expr::now());
+        assert_eq!(
+            insert.sql(),
+            "INSERT IGNORE INTO user (username, create_time) VALUES (?, NOW())"
+        );
+        assert_eq!(values(insert.values()), r#"[Str("a")]"#);
+        let insert = InsertWrapper::<User>::new(&pool)
+            .replace()
+            .set(User::id_field, 1u64);
+        assert_eq!(insert.sql(), "REPLACE INTO user (ID) VALUES (?)");
+    }
+
+    #[tokio::test]
+    async fn insert_select() {
+        let pool = pool();
+        let query = QueryWrapper::<User>::new(&pool)
+            .select_field(&["username", "age"])
+            .gt(User::age_field, 18);
+        let insert = InsertWrapper::<User>::new(&pool)
+            .insert_select((User::username_field, User::age_field), query);
+        assert_eq!(
+            insert.sql(),
+            "INSERT INTO user (username, age) SELECT username, age FROM user WHERE age > ?"
+        );
+        assert_eq!(values(insert.values()), "[I32(18)]");
+        assert!(insert.invalid.is_none());
+    }
+
+    #[tokio::test]
+    async fn insert_select_rejects_set() {
+        let pool = pool();
+        let query = || QueryWrapper::<User>::new(&pool).select(vec![User::username_field]);
+        let result = InsertWrapper::<User>::new(&pool)
+            .set(User::age_field, 1)
+            .insert_select((User::username_field,), query())
+            .execute()
+            .await;
+        assert!(matches!(result, Err(Error::InvalidArgument(_))));
+        let result = InsertWrapper::<User>::new(&pool)
+            .insert_select((User::username_field,), query())
+            .set(User::age_field, 1)
+            .execute()
+            .await;
+        assert!(matches!(result, Err(Error::InvalidArgument(_))));
+    }
+
+    #[tokio::test]
+    async fn large_in_lists_split_update_and_delete() {
+        let pool = pool();
+        let ids = (0..BIND_CHUNK_SIZE as u64 + 1).collect::<HashSet<_>>();
+        let mut delete = DeleteWrapper::<User>::new(&pool)
+            .eq(User::age_field, 0)
+            .r#in(User::id_field, ids.clone());
+        let statements = delete.statements();
+        assert_eq!(statements.len(), 2);
+        assert_eq!(
+            statements[1].0,
+            "DELETE FROM user WHERE age = ? AND ID IN (?)"
+        );
+        assert_eq!(statements[0].1.len(), BIND_CHUNK_SIZE + 1);
+        // the full list is back for the next run
+        assert_eq!(delete.values().len(), BIND_CHUNK_SIZE + 2);
+
+        let mut update = UpdateWrapper::<User>::new(&pool)
+            .incr(User::age_field, 1)
+            .r#in(User::id_field, ids.clone());
+        let statements = update.statements();
+        assert_eq!(statements.len(), 2);
+        assert_eq!(
+            statements[1].0,
+            "UPDATE user SET age = (age + ?) WHERE ID IN (?)"
+        );
+        assert_eq!(statements[1].1.len(), 2);
+
+        // a LIMIT or an OR would change the rows matched by the statements together
+        let mut limited = DeleteWrapper::<User>::new(&pool)
+            .r#in(User::id_field, ids.clone())
+            .limit(10);
+        assert_eq!(limited.statements().len(), 1);
+        let mut or = DeleteWrapper::<User>::new(&pool)
+            .eq(User::age_field, 0)
+            .or()
+            .r#in(User::id_field, ids);
+        assert_eq!(or.statements().len(), 1);
+    }
+}
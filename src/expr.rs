@@ -0,0 +1,629 @@
+use crate::IntoField;
+use crate::wrapper::SqlValue;
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, BitAnd, BitOr, BitXor, Div, Mul, Rem, Shl, Shr, Sub};
+
+/// sql expression with its bound values, built from lambda fields, values and functions.
+///
+/// ```ignore
+/// // (price * qty) AS total
+/// Expr::col(Order::price_field) * Expr::col(Order::qty_field)
+/// // ROW_NUMBER() OVER (PARTITION BY group_id ORDER BY score DESC)
+/// Expr::row_number().over(
+///     Window::new()
+///         .partition_by(Expr::col(User::group_id_field))
+///         .order_by(OrderBy::desc(Expr::col(User::score_field))),
+/// )
+/// ```
+#[derive(Debug, Clone)]
+pub struct Expr {
+    sql: String,
+    values: Vec<SqlValue>,
+}
+
+/// operand of an expression, an `Expr` or a value bound as `?`.
+pub trait IntoExpr {
+    fn into_expr(self) -> Expr;
+}
+
+impl IntoExpr for Expr {
+    fn into_expr(self) -> Expr {
+        self
+    }
+}
+
+impl<V> IntoExpr for V
+where
+    V: Into<SqlValue>,
+{
+    fn into_expr(self) -> Expr {
+        Expr::value(self)
+    }
+}
+
+impl Expr {
+    fn new(sql: String, values: Vec<SqlValue>) -> Self {
+        Self { sql, values }
+    }
+
+    pub fn col<'a, E, F>(field_func: F) -> Self
+    where
+        F: IntoField<'a, E>,
+    {
+        Self::raw(field_func.into_field())
+    }
+
+    /// sql used as is, e.g. a qualified column `u.id`.
+    pub fn raw(sql: &str) -> Self {
+        Self::new(sql.to_string(), vec![])
+    }
+
+    /// sql with `?` placeholders.
+    pub fn raw_values<V>(sql: &str, values: Vec<V>) -> Self
+    where
+        V: Into<SqlValue>,
+    {
+        Self::new(
+            sql.to_string(),
+            values.into_iter().map(Into::into).collect(),
+        )
+    }
+
+    /// bound value `?`.
+    pub fn value<V>(value: V) -> Self
+    where
+        V: Into<SqlValue>,
+    {
+        Self::new("?".to_string(), vec![value.into()])
+    }
+
+    /// bound value, JSON documents are bound as `CAST(? AS JSON)` so they aren't taken as strings.
+    pub fn json<V>(value: V) -> Self
+    where
+        V: Into<SqlValue>,
+    {
+        let value = value.into();
+        if value.is_json() {
+            Self::new("CAST(? AS JSON)".to_string(), vec![value])
+        } else {
+            Self::new("?".to_string(), vec![value])
+        }
+    }
+
+    /// `NAME(arg, ..)`
+    pub fn func(name: &str, args: Vec<Expr>) -> Self {
+        let mut values = vec![];
+        let args = args
+            .into_iter()
+            .map(|arg| {
+                values.extend(arg.values);
+                arg.sql
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        Self::new(format!("{name}({args})"), values)
+    }
+
+    pub fn sql(&self) -> &str {
+        &self.sql
+    }
+
+    pub fn values(&self) -> &[SqlValue] {
+        &self.values
+    }
+
+    pub(crate) fn into_parts(self) -> (String, Vec<SqlValue>) {
+        (self.sql, self.values)
+    }
+
+    fn is_null_value(&self) -> bool {
+        self.sql == "?" && matches!(self.values.as_slice(), [SqlValue::Null])
+    }
+
+    fn binary(self, operator: &str, rhs: impl IntoExpr) -> Self {
+        let rhs = rhs.into_expr();
+        let mut values = self.values;
+        values.extend(rhs.values);
+        Self::new(format!("({} {operator} {})", self.sql, rhs.sql), values)
+    }
+
+    fn range(self, operator: &str, low: impl IntoExpr, high: impl IntoExpr) -> Self {
+        let (low, high) = (low.into_expr(), high.into_expr());
+        let mut values = self.values;
+        values.extend(low.values);
+        values.extend(high.values);
+        Self::new(
+            format!("({} {operator} {} AND {})", self.sql, low.sql, high.sql),
+            values,
+        )
+    }
+
+    fn list<V>(self, operator: &str, items: Vec<V>, empty: &str) -> Self
+    where
+        V: IntoExpr,
+    {
+        if items.is_empty() {
+            return Self::raw(empty);
+        }
+        let mut values = self.values;
+        let items = items
+            .into_iter()
+            .map(|item| {
+                let item = item.into_expr();
+                values.extend(item.values);
+                item.sql
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        Self::new(format!("({} {operator} ({items}))", self.sql), values)
+    }
+
+    fn suffix(self, suffix: &str) -> Self {
+        Self::new(format!("{}{suffix}", self.sql), self.values)
+    }
+
+    /// a bound `NULL` renders `IS NULL`, like the wrapper's `eq`.
+    pub fn eq(self, rhs: impl IntoExpr) -> Self {
+        let rhs = rhs.into_expr();
+        if rhs.is_null_value() {
+            return self.is_null();
+        }
+        self.binary("=", rhs)
+    }
+
+    /// null-safe equal `<=>`
+    pub fn eq_null_safe(self, rhs: impl IntoExpr) -> Self {
+        self.binary("<=>", rhs)
+    }
+
+    /// a bound `NULL` renders `IS NOT NULL`, like the wrapper's `ne`.
+    pub fn ne(self, rhs: impl IntoExpr) -> Self {
+        let rhs = rhs.into_expr();
+        if rhs.is_null_value() {
+            return self.is_not_null();
+        }
+        self.binary("<>", rhs)
+    }
+
+    pub fn gt(self, rhs: impl IntoExpr) -> Self {
+        self.binary(">", rhs)
+    }
+
+    pub fn ge(self, rhs: impl IntoExpr) -> Self {
+        self.binary(">=", rhs)
+    }
+
+    pub fn lt(self, rhs: impl IntoExpr) -> Self {
+        self.binary("<", rhs)
+    }
+
+    pub fn le(self, rhs: impl IntoExpr) -> Self {
+        self.binary("<=", rhs)
+    }
+
+    pub fn and(self, rhs: impl IntoExpr) -> Self {
+        self.binary("AND", rhs)
+    }
+
+    pub fn or(self, rhs: impl IntoExpr) -> Self {
+        self.binary("OR", rhs)
+    }
+
+    pub fn between(self, low: impl IntoExpr, high: impl IntoExpr) -> Self {
+        self.range("BETWEEN", low, high)
+    }
+
+    pub fn not_between(self, low: impl IntoExpr, high: impl IntoExpr) -> Self {
+        self.range("NOT BETWEEN", low, high)
+    }
+
+    /// `LIKE`, the pattern is used as is.
+    pub fn like(self, pattern: impl IntoExpr) -> Self {
+        self.binary("LIKE", pattern)
+    }
+
+    pub fn not_like(self, pattern: impl IntoExpr) -> Self {
+        self.binary("NOT LIKE", pattern)
+    }
+
+    /// `IN (..)`, an empty list is false.
+    pub fn in_list<V>(self, values: Vec<V>) -> Self
+    where
+        V: IntoExpr,
+    {
+        self.list("IN", values, "(1 = 0)")
+    }
+
+    /// `NOT IN (..)`, an empty list is true.
+    pub fn not_in_list<V>(self, values: Vec<V>) -> Self
+    where
+        V: IntoExpr,
+    {
+        self.list("NOT IN", values, "(1 = 1)")
+    }
+
+    pub fn is_null(self) -> Self {
+        self.suffix(" IS NULL")
+    }
+
+    pub fn is_not_null(self) -> Self {
+        self.suffix(" IS NOT NULL")
+    }
+
+    pub fn lower(self) -> Self {
+        Self::func("LOWER", vec![self])
+    }
+
+    pub fn upper(self) -> Self {
+        Self::func("UPPER", vec![self])
+    }
+
+    pub fn date(self) -> Self {
+        Self::func("DATE", vec![self])
+    }
+
+    pub fn now() -> Self {
+        Self::raw("NOW()")
+    }
+
+    pub fn coalesce(self, rhs: impl IntoExpr) -> Self {
+        Self::func("COALESCE", vec![self, rhs.into_expr()])
+    }
+
+    pub fn count_all() -> Self {
+        Self::raw("COUNT(*)")
+    }
+
+    pub fn count(self) -> Self {
+        Self::func("COUNT", vec![self])
+    }
+
+    pub fn sum(self) -> Self {
+        Self::func("SUM", vec![self])
+    }
+
+    pub fn avg(self) -> Self {
+        Self::func("AVG", vec![self])
+    }
+
+    pub fn max(self) -> Self {
+        Self::func("MAX", vec![self])
+    }
+
+    pub fn min(self) -> Self {
+        Self::func("MIN", vec![self])
+    }
+
+    /// `JSON_EXTRACT(expr, path)`, the path is bound, e.g. `$.theme`.
+    pub fn json_extract(self, path: &str) -> Self {
+        Self::func("JSON_EXTRACT", vec![self, Self::value(path)])
+    }
+
+    pub fn json_unquote(self) -> Self {
+        Self::func("JSON_UNQUOTE", vec![self])
+    }
+
+    /// `expr->>path`, the unquoted value at `path`.
+    pub fn json_value(self, path: &str) -> Self {
+        self.json_extract(path).json_unquote()
+    }
+
+    pub fn json_contains(self, candidate: impl IntoExpr) -> Self {
+        Self::func("JSON_CONTAINS", vec![self, candidate.into_expr()])
+    }
+
+    pub fn json_overlaps(self, other: impl IntoExpr) -> Self {
+        Self::func("JSON_OVERLAPS", vec![self, other.into_expr()])
+    }
+
+    /// `expr MEMBER OF(array)`
+    pub fn member_of(self, array: impl IntoExpr) -> Self {
+        let array = array.into_expr();
+        let mut values = self.values;
+        values.extend(array.values);
+        Self::new(format!("({} MEMBER OF({}))", self.sql, array.sql), values)
+    }
+
+    /// `MATCH (a, b) AGAINST (? IN .. MODE)`, the relevance score, non-zero when the row matches.
+    pub fn match_against(fields: &[&str], query: &str, mode: MatchMode) -> Self {
+        let query = match mode {
+            MatchMode::Boolean => boolean_terms(query),
+            _ => query.to_string(),
+        };
+        Self::new(
+            format!(
+                "MATCH ({}) AGAINST (? {})",
+                fields.join(", "),
+                mode.to_str()
+            ),
+            vec![query.into()],
+        )
+    }
+
+    pub fn row_number() -> Self {
+        Self::raw("ROW_NUMBER()")
+    }
+
+    pub fn rank() -> Self {
+        Self::raw("RANK()")
+    }
+
+    pub fn dense_rank() -> Self {
+        Self::raw("DENSE_RANK()")
+    }
+
+    /// `LAG(expr, offset)`, the value `offset` rows before in the window.
+    pub fn lag(self, offset: u64) -> Self {
+        Self::func("LAG", vec![self, Self::raw(&offset.to_string())])
+    }
+
+    /// `LEAD(expr, offset)`, the value `offset` rows after in the window.
+    pub fn lead(self, offset: u64) -> Self {
+        Self::func("LEAD", vec![self, Self::raw(&offset.to_string())])
+    }
+
+    /// `expr OVER (window)`
+    pub fn over(self, window: Window) -> Self {
+        let (window_sql, window_values) = window.into_parts();
+        let mut values = self.values;
+        values.extend(window_values);
+        Self::new(format!("{} OVER ({window_sql})", self.sql), values)
+    }
+}
+
+/// arithmetic and bitwise operators, `a * b` renders `(a * b)`.
+macro_rules! impl_binary_ops {
+    ($($trait:ident $method:ident $operator:literal),*) => {
+        $(
+            impl<R: IntoExpr> $trait<R> for Expr {
+                type Output = Expr;
+
+                fn $method(self, rhs: R) -> Expr {
+                    self.binary($operator, rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_binary_ops!(
+    Add add "+",
+    Sub sub "-",
+    Mul mul "*",
+    Div div "/",
+    Rem rem "%",
+    BitAnd bitand "&",
+    BitOr bitor "|",
+    BitXor bitxor "^",
+    Shl shl "<<",
+    Shr shr ">>"
+);
+
+impl Display for Expr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.sql)
+    }
+}
+
+pub enum OrderBy {
+    Asc(Expr),
+    Desc(Expr),
+}
+
+impl OrderBy {
+    pub fn asc(expr: Expr) -> Self {
+        OrderBy::Asc(expr)
+    }
+
+    pub fn desc(expr: Expr) -> Self {
+        OrderBy::Desc(expr)
+    }
+
+    pub(crate) fn into_parts(self) -> (String, Vec<SqlValue>) {
+        match self {
+            OrderBy::Asc(expr) => (format!("{} ASC", expr.sql), expr.values),
+            OrderBy::Desc(expr) => (format!("{} DESC", expr.sql), expr.values),
+        }
+    }
+}
+
+/// search modifier of `MATCH .. AGAINST`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchMode {
+    NaturalLanguage,
+    /// natural language, searched again with the words of the most relevant rows.
+    QueryExpansion,
+    /// user input in boolean mode, every word is quoted so `+ - > < ( ) ~ * " @` match literally.
+    Boolean,
+    /// trusted boolean expression used as is, e.g. `+rust -java`.
+    BooleanRaw,
+}
+
+impl MatchMode {
+    fn to_str(self) -> &'static str {
+        match self {
+            MatchMode::NaturalLanguage => "IN NATURAL LANGUAGE MODE",
+            MatchMode::QueryExpansion => "IN NATURAL LANGUAGE MODE WITH QUERY EXPANSION",
+            MatchMode::Boolean | MatchMode::BooleanRaw => "IN BOOLEAN MODE",
+        }
+    }
+}
+
+/// `rust (async)` -> `"rust" "(async)"`, quotes in the input are dropped.
+fn boolean_terms(query: &str) -> String {
+    query
+        .split_whitespace()
+        .map(|term| term.replace('"', ""))
+        .filter(|term| !term.is_empty())
+        .map(|term| format!("\"{term}\""))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// `PARTITION BY .. ORDER BY ..` of a window function.
+#[derive(Default)]
+pub struct Window {
+    partition: Vec<Expr>,
+    order: Vec<OrderBy>,
+}
+
+impl Window {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn partition_by(mut self, expr: Expr) -> Self {
+        self.partition.push(expr);
+        self
+    }
+
+    pub fn order_by(mut self, order: OrderBy) -> Self {
+        self.order.push(order);
+        self
+    }
+
+    fn into_parts(self) -> (String, Vec<SqlValue>) {
+        let mut sql = vec![];
+        let mut values = vec![];
+        if !self.partition.is_empty() {
+            let partition = self
+                .partition
+                .into_iter()
+                .map(|expr| {
+                    values.extend(expr.values);
+                    expr.sql
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            sql.push(format!("PARTITION BY {partition}"));
+        }
+        if !self.order.is_empty() {
+            let order = self
+                .order
+                .into_iter()
+                .map(|order| {
+                    let (order_sql, order_values) = order.into_parts();
+                    values.extend(order_values);
+                    order_sql
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            sql.push(format!("ORDER BY {order}"));
+        }
+        (sql.join(" "), values)
+    }
+}
+
+/// `CASE WHEN .. THEN .. ELSE .. END`
+#[derive(Default)]
+pub struct Case {
+    sql: String,
+    values: Vec<SqlValue>,
+}
+
+impl Case {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn when(mut self, condition: Expr, then: impl IntoExpr) -> Self {
+        let then = then.into_expr();
+        self.sql += &format!(" WHEN {} THEN {}", condition.sql, then.sql);
+        self.values.extend(condition.values);
+        self.values.extend(then.values);
+        self
+    }
+
+    pub fn otherwise(mut self, value: impl IntoExpr) -> Self {
+        let value = value.into_expr();
+        self.sql += &format!(" ELSE {}", value.sql);
+        self.values.extend(value.values);
+        self
+    }
+
+    pub fn end(self) -> Expr {
+        Expr::new(format!("CASE{} END", self.sql), self.values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::User;
+
+    #[test]
+    fn window_functions() {
+        let expr = Expr::row_number().over(
+            Window::new()
+                .partition_by(Expr::col(User::age_field))
+                .order_by(OrderBy::desc(Expr::col(User::id_field))),
+        );
+        assert_eq!(
+            expr.to_string(),
+            "ROW_NUMBER() OVER (PARTITION BY age ORDER BY ID DESC)"
+        );
+        let expr = Expr::col(User::age_field)
+            .lag(1)
+            .over(Window::new().order_by(OrderBy::asc(Expr::col(User::id_field))));
+        assert_eq!(expr.to_string(), "LAG(age, 1) OVER (ORDER BY ID ASC)");
+        assert_eq!(
+            Expr::rank().over(Window::new()).to_string(),
+            "RANK() OVER ()"
+        );
+    }
+
+    #[test]
+    fn case_binds_in_order() {
+        let expr = Case::new()
+            .when(Expr::col(User::age_field).lt(18), "minor")
+            .otherwise("adult")
+            .end();
+        assert_eq!(expr.to_string(), "CASE WHEN (age < ?) THEN ? ELSE ? END");
+        assert_eq!(
+            format!("{:?}", expr.values),
+            format!(
+                "{:?}",
+                vec![
+                    SqlValue::from(18),
+                    SqlValue::from("minor"),
+                    SqlValue::from("adult")
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn null_values_compare_with_is_null() {
+        let expr = Expr::col(User::age_field).eq(None::<i32>);
+        assert_eq!(expr.to_string(), "age IS NULL");
+        assert!(expr.values.is_empty());
+        let expr = Expr::col(User::age_field).ne(SqlValue::Null);
+        assert_eq!(expr.to_string(), "age IS NOT NULL");
+        let expr = Expr::col(User::age_field).eq_null_safe(SqlValue::Null);
+        assert_eq!(expr.to_string(), "(age <=> ?)");
+        assert_eq!(
+            Expr::col(User::age_field).eq(Some(3)).to_string(),
+            "(age = ?)"
+        );
+    }
+
+    #[test]
+    fn match_against_modes() {
+        let expr = Expr::match_against(&["username"], "rust +\"async\" (io)", MatchMode::Boolean);
+        assert_eq!(
+            expr.to_string(),
+            "MATCH (username) AGAINST (? IN BOOLEAN MODE)"
+        );
+        assert_eq!(
+            format!("{:?}", expr.values),
+            r#"[Str("\"rust\" \"+async\" \"(io)\"")]"#
+        );
+        let expr = Expr::match_against(&["a", "b"], "+rust -java", MatchMode::BooleanRaw);
+        assert_eq!(format!("{:?}", expr.values), r#"[Str("+rust -java")]"#);
+        let expr = Expr::match_against(&["a"], "rust", MatchMode::QueryExpansion);
+        assert_eq!(
+            expr.to_string(),
+            "MATCH (a) AGAINST (? IN NATURAL LANGUAGE MODE WITH QUERY EXPANSION)"
+        );
+    }
+}
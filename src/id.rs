@@ -0,0 +1,184 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, PoisonError};
+use std::time::{SystemTime, UNIX_EPOCH};
+use uuid::Uuid;
+
+/// generates primary keys of type `T` before insert.
+///
+/// selected per entity with `#[entity(id_generator = "..")]`, `snowflake`, `uuid_v4`, `uuid_v7`
+/// or the path of a static/function implementing `IdGenerator<T>`, e.g. `fn() -> String`.
+pub trait IdGenerator<T>: Send + Sync {
+    fn next_id(&self) -> T;
+}
+
+impl<T, F> IdGenerator<T> for F
+where
+    F: Fn() -> T + Send + Sync,
+{
+    fn next_id(&self) -> T {
+        self()
+    }
+}
+
+/// generator used by `#[entity(id_generator = "snowflake")]`, worker id 0 until `set_worker_id`.
+pub static SNOWFLAKE: Snowflake = Snowflake::new(0);
+
+/// 2020-01-01T00:00:00Z in milliseconds.
+const SNOWFLAKE_EPOCH: u64 = 1_577_836_800_000;
+const WORKER_ID_BITS: u64 = 10;
+const SEQUENCE_BITS: u64 = 12;
+pub const MAX_WORKER_ID: u64 = (1 << WORKER_ID_BITS) - 1;
+const MAX_SEQUENCE: u64 = (1 << SEQUENCE_BITS) - 1;
+
+/// 41 bits milliseconds since 2020-01-01, 10 bits worker id, 12 bits sequence.
+///
+/// ids keep increasing when the clock moves back, the last timestamp is reused.
+pub struct Snowflake {
+    worker_id: AtomicU64,
+    /// (last timestamp, sequence)
+    state: Mutex<(u64, u64)>,
+}
+
+impl Snowflake {
+    /// panics when `worker_id` is greater than `MAX_WORKER_ID`.
+    pub const fn new(worker_id: u64) -> Self {
+        assert!(
+            worker_id <= MAX_WORKER_ID,
+            "snowflake worker id out of range"
+        );
+        Self {
+            worker_id: AtomicU64::new(worker_id),
+            state: Mutex::new((0, 0)),
+        }
+    }
+
+    /// panics when `worker_id` is greater than `MAX_WORKER_ID`.
+    pub fn set_worker_id(&self, worker_id: u64) {
+        assert!(
+            worker_id <= MAX_WORKER_ID,
+            "snowflake worker id out of range"
+        );
+        self.worker_id.store(worker_id, Ordering::Relaxed);
+    }
+
+    pub fn worker_id(&self) -> u64 {
+        self.worker_id.load(Ordering::Relaxed)
+    }
+
+    pub fn next(&self) -> u64 {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        let (last_timestamp, sequence) = *state;
+        let mut timestamp = current_millis().max(last_timestamp);
+        let mut next_sequence = 0;
+        if timestamp == last_timestamp {
+            next_sequence = (sequence + 1) & MAX_SEQUENCE;
+            if next_sequence == 0 {
+                // sequence exhausted, borrow the next millisecond
+                timestamp += 1;
+            }
+        }
+        *state = (timestamp, next_sequence);
+        ((timestamp - SNOWFLAKE_EPOCH) << (WORKER_ID_BITS + SEQUENCE_BITS))
+            | (self.worker_id() << SEQUENCE_BITS)
+            | next_sequence
+    }
+}
+
+fn current_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(SNOWFLAKE_EPOCH, |duration| duration.as_millis() as u64)
+        .max(SNOWFLAKE_EPOCH)
+}
+
+impl IdGenerator<u64> for Snowflake {
+    fn next_id(&self) -> u64 {
+        self.next()
+    }
+}
+
+impl IdGenerator<i64> for Snowflake {
+    fn next_id(&self) -> i64 {
+        self.next() as i64
+    }
+}
+
+impl IdGenerator<String> for Snowflake {
+    fn next_id(&self) -> String {
+        self.next().to_string()
+    }
+}
+
+/// random uuid.
+pub struct UuidV4;
+
+impl IdGenerator<Uuid> for UuidV4 {
+    fn next_id(&self) -> Uuid {
+        Uuid::new_v4()
+    }
+}
+
+impl IdGenerator<String> for UuidV4 {
+    fn next_id(&self) -> String {
+        Uuid::new_v4().to_string()
+    }
+}
+
+/// time ordered uuid, friendlier to the primary key index than v4.
+pub struct UuidV7;
+
+impl IdGenerator<Uuid> for UuidV7 {
+    fn next_id(&self) -> Uuid {
+        Uuid::now_v7()
+    }
+}
+
+impl IdGenerator<String> for UuidV7 {
+    fn next_id(&self) -> String {
+        Uuid::now_v7().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Entity;
+    use batis4sqlx_macros::{Entity, entity};
+    use sqlx::FromRow;
+
+    #[entity(table_name = "doc", id_generator = "uuid_v7")]
+    #[derive(FromRow, Default, Debug, Entity)]
+    struct Doc {
+        #[entity_field(primary_key)]
+        id: Option<String>,
+        title: Option<String>,
+    }
+
+    #[test]
+    fn snowflake_ids_increase_and_carry_the_worker_id() {
+        let snowflake = Snowflake::new(3);
+        let ids = (0..10_000).map(|_| snowflake.next()).collect::<Vec<_>>();
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(
+            ids.iter()
+                .all(|id| (id >> SEQUENCE_BITS) & MAX_WORKER_ID == 3)
+        );
+    }
+
+    #[test]
+    fn uuid_generators() {
+        assert_eq!(IdGenerator::<Uuid>::next_id(&UuidV4).get_version_num(), 4);
+        assert_eq!(IdGenerator::<Uuid>::next_id(&UuidV7).get_version_num(), 7);
+    }
+
+    #[test]
+    fn generate_primary_key_fills_none_only() {
+        let mut doc = Doc::default();
+        doc.generate_primary_key();
+        let id = doc.id.clone().unwrap();
+        assert_eq!(Uuid::parse_str(&id).unwrap().get_version_num(), 7);
+        doc.generate_primary_key();
+        assert_eq!(doc.id.as_deref(), Some(id.as_str()));
+        assert!(doc.title.is_none());
+    }
+}
@@ -1,13 +1,19 @@
 pub extern crate batis4sqlx_macros;
 extern crate core;
+// the macros expand to `batis4sqlx::..` paths.
+#[cfg(test)]
+extern crate self as batis4sqlx;
 
-use crate::wrapper::{SqlValue, Wrapper};
+use crate::wrapper::{SqlTuple, SqlValue, Wrapper};
 use sqlx::mysql::MySqlRow;
 use sqlx::{Error, FromRow, MySqlPool};
 use std::fmt::{Display, Formatter};
+use std::marker::PhantomData;
 use std::ops::Deref;
 
 pub mod chain;
+pub mod expr;
+pub mod id;
 pub mod repository;
 pub mod wrapper;
 
@@ -17,6 +23,80 @@ pub trait Entity {
     fn table_name() -> &'static str;
 
     fn primary_key<'b>() -> LambdaField<'b>;
+
+    /// all primary key columns in declaration order, more than 1 for composite keys.
+    fn primary_keys<'b>() -> Vec<LambdaField<'b>> {
+        vec![Self::primary_key()]
+    }
+
+    /// fills a `None` primary key from `#[entity(id_generator = "..")]`, called by save before insert.
+    fn generate_primary_key(&mut self) {}
+
+    /// all struct fields in declaration order, including skipped ones, empty unless generated by `#[entity]`.
+    fn columns() -> &'static [Column] {
+        &[]
+    }
+
+    /// values of `columns()` in the same order, `SqlValue::Null` for `None` and skipped fields.
+    fn values(&self) -> Vec<SqlValue> {
+        vec![]
+    }
+
+    /// column by column name or rust field name.
+    fn column(name: &str) -> Option<&'static Column> {
+        Self::columns()
+            .iter()
+            .find(|column| column.name == name || column.field == name)
+    }
+}
+
+/// column metadata of an entity field, generated by `#[entity]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Column {
+    /// rust field name.
+    pub field: &'static str,
+    /// column name, `#[entity_field(name = "..")]` or the field name.
+    pub name: &'static str,
+    /// mysql type, `#[entity_field(sql_type = "..")]` or derived from the rust type, empty when unknown.
+    pub sql_type: &'static str,
+    /// `Option` field that is neither the primary key nor `not_null`.
+    pub nullable: bool,
+    pub primary_key: bool,
+    /// `#[entity_field(skip)]`, not a table column.
+    pub skip: bool,
+    /// `#[entity_field(default = "..")]` column default expression.
+    pub default: Option<&'static str>,
+    /// `#[entity_field(update_strategy = "..")]`, `None` follows the strategy of the update call.
+    pub update_strategy: Option<UpdateStrategy>,
+}
+
+/// when `update(&entity, wrapper)` sets a field, declared per field with
+/// `#[entity_field(update_strategy = "always" | "not_null" | "not_empty" | "never")]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpdateStrategy {
+    /// always set, `None` sets NULL.
+    Always,
+    /// skip `None`.
+    NotNull,
+    /// skip `None` and empty strings.
+    NotEmpty,
+    /// never set, e.g. `create_time`.
+    Never,
+}
+
+impl UpdateStrategy {
+    pub fn should_set(self, value: &SqlValue) -> bool {
+        match self {
+            UpdateStrategy::Always => true,
+            UpdateStrategy::NotNull => !matches!(value, SqlValue::Null),
+            UpdateStrategy::NotEmpty => match value {
+                SqlValue::Null => false,
+                SqlValue::Str(value) => !value.is_empty(),
+                _ => true,
+            },
+            UpdateStrategy::Never => false,
+        }
+    }
 }
 
 pub struct LambdaField<'a>(&'a str);
@@ -41,6 +121,128 @@ impl<'a> Display for LambdaField<'a> {
     }
 }
 
+/// typed lambda field, `E` is the owning entity and `T` the rust value type of the column.
+pub struct Field<E, T> {
+    name: &'static str,
+    _ignore: PhantomData<fn() -> (E, T)>,
+}
+
+impl<E, T> Field<E, T> {
+    pub const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            _ignore: PhantomData,
+        }
+    }
+}
+
+impl<E, T> Clone for Field<E, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<E, T> Copy for Field<E, T> {}
+
+impl<E, T> Deref for Field<E, T> {
+    type Target = &'static str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.name
+    }
+}
+
+impl<E, T> Display for Field<E, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+/// value type of an untyped `LambdaField`, accepts any value.
+pub struct Untyped;
+
+/// field accepted by the lambda wrappers of entity `E`.
+///
+/// implemented for `Field<E, T>`, for functions returning it such as the generated
+/// `User::id_field`, and for the untyped `LambdaField`.
+pub trait IntoField<'a, E> {
+    type Value;
+
+    fn into_field(self) -> &'a str;
+}
+
+impl<'a, E, T> IntoField<'a, E> for Field<E, T> {
+    type Value = T;
+
+    fn into_field(self) -> &'a str {
+        self.name
+    }
+}
+
+impl<'a, E, T, F> IntoField<'a, E> for F
+where
+    F: FnOnce() -> Field<E, T>,
+{
+    type Value = T;
+
+    fn into_field(self) -> &'a str {
+        self().name
+    }
+}
+
+impl<'a, E> IntoField<'a, E> for LambdaField<'a> {
+    type Value = Untyped;
+
+    fn into_field(self) -> &'a str {
+        self.0
+    }
+}
+
+/// fields of a tuple condition, a tuple of fields of entity `E` or a `Vec<LambdaField>`.
+pub trait IntoFields<'a, E> {
+    fn into_fields(self) -> Vec<&'a str>;
+}
+
+impl<'a, E> IntoFields<'a, E> for Vec<LambdaField<'a>> {
+    fn into_fields(self) -> Vec<&'a str> {
+        self.into_iter().map(|field| field.0).collect()
+    }
+}
+
+macro_rules! impl_into_fields {
+    ($($name:ident),+) => {
+        impl<'a, E, $($name),+> IntoFields<'a, E> for ($($name,)+)
+        where
+            $($name: IntoField<'a, E>,)+
+        {
+            #[allow(non_snake_case)]
+            fn into_fields(self) -> Vec<&'a str> {
+                let ($($name,)+) = self;
+                vec![$($name.into_field()),+]
+            }
+        }
+    };
+}
+
+impl_into_fields!(A);
+impl_into_fields!(A, B);
+impl_into_fields!(A, B, C);
+impl_into_fields!(A, B, C, D);
+
+/// values accepted by a field whose rust type is `T`.
+#[diagnostic::on_unimplemented(message = "`{Self}` is not a value of a `{T}` field")]
+pub trait FieldValue<T> {}
+
+impl<T> FieldValue<T> for T {}
+
+impl<T> FieldValue<T> for Option<T> {}
+
+impl FieldValue<String> for &str {}
+
+impl FieldValue<String> for &String {}
+
+impl<V> FieldValue<Untyped> for V where V: Into<SqlValue> {}
+
 pub trait ServiceImpl<'a, 'd, E: Entity + for<'r> FromRow<'r, MySqlRow> + Send + Unpin> {
     fn borrow_db(&self) -> &MySqlPool;
 
@@ -52,21 +254,174 @@ pub trait ServiceImpl<'a, 'd, E: Entity + for<'r> FromRow<'r, MySqlRow> + Send +
         chain::UpdateWrapper::<E>::new(self.borrow_db())
     }
 
+    fn lambda_insert(&'d self) -> chain::InsertWrapper<'a, 'd, E> {
+        chain::InsertWrapper::<E>::new(self.borrow_db())
+    }
+
     fn lambda_delete(&'d self) -> chain::DeleteWrapper<'a, 'd, E> {
         chain::DeleteWrapper::<E>::new(self.borrow_db())
     }
 
-    fn get_by_primary_key<K>(&'d self, primary_key_value: K) -> impl Future<Output = Result<Option<E>>>
+    /// `K` is a tuple such as `(user_id, role_id)` for composite primary keys.
+    fn get_by_primary_key<K>(
+        &'d self,
+        primary_key_value: K,
+    ) -> impl Future<Output = Result<Option<E>>>
     where
-        K: Into<SqlValue> + Clone,
+        K: SqlTuple,
     {
-        self.lambda_query()
-            .eq(E::primary_key, primary_key_value)
-            .last("LIMIT 1")
-            .opt()
+        let primary_keys = E::primary_keys();
+        let values = primary_key_value.into_values();
+        let arity = repository::check_primary_key_arity(primary_keys.len(), values.len());
+        let mut query = self.lambda_query();
+        for (primary_key, value) in primary_keys.into_iter().zip(values) {
+            query = query.eq(primary_key, value);
+        }
+        async move {
+            arity?;
+            query.first_row().await
+        }
+    }
+
+    /// sets the fields of `entity` on the rows matching `wrapper`, `None` fields are skipped.
+    ///
+    /// fields with `#[entity_field(update_strategy = "..")]` follow their own strategy,
+    /// primary keys and skipped fields are never set.
+    fn update(
+        &'d self,
+        entity: &E,
+        wrapper: chain::UpdateWrapper<'a, 'd, E>,
+    ) -> impl Future<Output = Result<u64>> {
+        self.update_with(entity, wrapper, UpdateStrategy::NotNull)
+    }
+
+    /// `update` with the strategy of fields without their own, `UpdateStrategy::Always` sets `None` to NULL.
+    fn update_with(
+        &'d self,
+        entity: &E,
+        wrapper: chain::UpdateWrapper<'a, 'd, E>,
+        strategy: UpdateStrategy,
+    ) -> impl Future<Output = Result<u64>> {
+        wrapper.set_entity(entity, strategy).execute()
     }
 
     fn vec(&'d self) -> impl Future<Output = Result<Vec<E>>> {
         self.lambda_query().vec()
     }
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::wrapper::SqlValue;
+    use crate::{Entity, UpdateStrategy};
+    use batis4sqlx_macros::{Entity, entity};
+    use sqlx::{FromRow, MySqlPool};
+
+    #[entity(table_name = "user")]
+    #[derive(FromRow, Default, Debug, Entity)]
+    pub(crate) struct User {
+        #[entity_field(primary_key, name = "ID")]
+        pub(crate) id: Option<u64>,
+        pub(crate) username: Option<String>,
+        pub(crate) age: Option<i32>,
+        #[entity_field(update_strategy = "never")]
+        pub(crate) create_time: Option<String>,
+        #[sqlx(skip)]
+        #[entity_field(skip)]
+        pub(crate) ignore: String,
+    }
+
+    #[entity(table_name = "user_role")]
+    #[derive(FromRow, Default, Debug, Entity)]
+    pub(crate) struct UserRole {
+        #[entity_field(primary_key)]
+        pub(crate) user_id: Option<u64>,
+        #[entity_field(primary_key)]
+        pub(crate) role_id: Option<u64>,
+    }
+
+    /// never connects, the wrappers only need it to be built.
+    pub(crate) fn pool() -> MySqlPool {
+        MySqlPool::connect_lazy("mysql://root@localhost/test").unwrap()
+    }
+
+    #[test]
+    fn entity_metadata() {
+        assert_eq!(User::table_name(), "user");
+        assert_eq!(*User::primary_key(), "ID");
+        let keys = UserRole::primary_keys();
+        assert_eq!(
+            keys.iter().map(|key| **key).collect::<Vec<_>>(),
+            ["user_id", "role_id"]
+        );
+        let ignore = User::column("ignore").unwrap();
+        assert!(ignore.skip);
+        assert!(User::default().ignore.is_empty());
+        assert_eq!(User::column("ID").unwrap().field, "id");
+    }
+
+    #[test]
+    fn update_strategy() {
+        let empty = SqlValue::Str(String::new());
+        assert!(UpdateStrategy::Always.should_set(&SqlValue::Null));
+        assert!(!UpdateStrategy::NotNull.should_set(&SqlValue::Null));
+        assert!(UpdateStrategy::NotNull.should_set(&empty));
+        assert!(!UpdateStrategy::NotEmpty.should_set(&empty));
+        assert!(!UpdateStrategy::Never.should_set(&SqlValue::I64(1)));
+    }
+
+    #[test]
+    fn typed_fields() {
+        use crate::{IntoField, IntoFields, LambdaField};
+        assert_eq!(*User::id_field(), "ID");
+        assert_eq!(
+            IntoField::<User>::into_field(User::username_field),
+            "username"
+        );
+        assert_eq!(
+            IntoField::<User>::into_field(LambdaField::new("age")),
+            "age"
+        );
+        let fields =
+            IntoFields::<UserRole>::into_fields((UserRole::user_id_field, UserRole::role_id_field));
+        assert_eq!(fields, ["user_id", "role_id"]);
+    }
+
+    #[test]
+    fn columns_and_values() {
+        let columns = User::columns();
+        assert_eq!(
+            columns.iter().map(|column| column.name).collect::<Vec<_>>(),
+            ["ID", "username", "age", "create_time", "ignore"]
+        );
+        let age = User::column("age").unwrap();
+        assert_eq!(age.sql_type, "INT");
+        assert!(age.nullable);
+        assert!(!User::column("ID").unwrap().nullable);
+        let user = User {
+            id: Some(1),
+            age: Some(18),
+            ..Default::default()
+        };
+        assert_eq!(
+            format!("{:?}", user.values()),
+            "[U64(1), Null, I32(18), Null, Null]"
+        );
+    }
+
+    #[test]
+    fn columns_default_to_empty() {
+        struct Plain;
+        impl Entity for Plain {
+            fn table_name() -> &'static str {
+                "plain"
+            }
+            fn primary_key<'b>() -> crate::LambdaField<'b> {
+                crate::LambdaField::new("id")
+            }
+        }
+        assert!(Plain::columns().is_empty());
+        assert!(Plain.values().is_empty());
+        assert!(Plain::column("id").is_none());
+    }
+}
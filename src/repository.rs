@@ -1,8 +1,16 @@
-use crate::Entity;
 use crate::wrapper::SqlValue;
-use sqlx::mysql::{MySqlArguments, MySqlRow};
-use sqlx::query::{Query, QueryAs, QueryScalar};
-use sqlx::{MySql, MySqlPool};
+use crate::{Entity, LambdaField, Result, UpdateStrategy};
+use sqlx::mysql::{MySqlArguments, MySqlConnection, MySqlRow};
+use sqlx::{Arguments, Error, MySqlPool};
+
+/// max values bound by one statement when a batch is split into several statements.
+pub const BIND_CHUNK_SIZE: usize = 10_000;
+
+/// sql and bound values of one statement.
+pub type Statement = (String, Vec<SqlValue>);
+
+/// max placeholders of one MySQL statement.
+pub const MAX_BIND_VALUES: usize = 65_535;
 
 pub trait MySqlRepository<
     E: Entity + for<'r> sqlx::FromRow<'r, MySqlRow> + Send + Unpin + serde::Serialize,
@@ -11,92 +19,349 @@ pub trait MySqlRepository<
     fn borrow_db(&self) -> &MySqlPool;
 }
 
-pub fn bind_query<'q>(
-    mut query: Query<'q, MySql, MySqlArguments>,
-    values: &Vec<SqlValue>,
-) -> Query<'q, MySql, MySqlArguments> {
+/// error when `values` exceed `MAX_BIND_VALUES`, e.g. an `IN` list too large for one statement.
+pub fn arguments(values: &[SqlValue]) -> Result<MySqlArguments> {
+    if values.len() > MAX_BIND_VALUES {
+        return Err(Error::InvalidArgument(format!(
+            "statement binds {} values, MySQL allows at most {MAX_BIND_VALUES}, split it into several statements",
+            values.len()
+        )));
+    }
+    let mut arguments = MySqlArguments::default();
     for value in values {
-        query = match value {
-            SqlValue::ISize(value) => query.bind(*value as i64),
-            SqlValue::USize(value) => query.bind(*value as u64),
-            SqlValue::I8(value) => query.bind(*value),
-            SqlValue::U8(value) => query.bind(*value),
-            SqlValue::I16(value) => query.bind(*value),
-            SqlValue::U16(value) => query.bind(*value),
-            SqlValue::I32(value) => query.bind(*value),
-            SqlValue::U32(value) => query.bind(*value),
-            SqlValue::I64(value) => query.bind(*value),
-            SqlValue::U64(value) => query.bind(*value),
-            SqlValue::F32(value) => query.bind(*value),
-            SqlValue::F64(value) => query.bind(*value),
-            SqlValue::Bool(value) => query.bind(*value),
-            SqlValue::Str(value) => query.bind(value.clone()),
-            SqlValue::Time(value) => query.bind(*value),
-            SqlValue::Date(value) => query.bind(*value),
-            SqlValue::DateTime(value) => query.bind(*value),
-            SqlValue::Decimal(value) => query.bind(*value),
-            &SqlValue::Null => query.bind(None::<&str>),
-        }
+        arguments.add(value).map_err(Error::Encode)?;
     }
-    query
+    Ok(arguments)
 }
 
-pub fn bind_query_as<'q, E: Entity>(
-    mut query_as: QueryAs<'q, MySql, E, MySqlArguments>,
-    values: &Vec<SqlValue>,
-) -> QueryAs<'q, MySql, E, MySqlArguments> {
-    for value in values {
-        query_as = match value {
-            SqlValue::ISize(value) => query_as.bind(*value as i64),
-            SqlValue::USize(value) => query_as.bind(*value as u64),
-            SqlValue::I8(value) => query_as.bind(*value),
-            SqlValue::U8(value) => query_as.bind(*value),
-            SqlValue::I16(value) => query_as.bind(*value),
-            SqlValue::U16(value) => query_as.bind(*value),
-            SqlValue::I32(value) => query_as.bind(*value),
-            SqlValue::U32(value) => query_as.bind(*value),
-            SqlValue::I64(value) => query_as.bind(*value),
-            SqlValue::U64(value) => query_as.bind(*value),
-            SqlValue::F32(value) => query_as.bind(*value),
-            SqlValue::F64(value) => query_as.bind(*value),
-            SqlValue::Bool(value) => query_as.bind(*value),
-            SqlValue::Str(value) => query_as.bind(value.clone()),
-            SqlValue::Time(value) => query_as.bind(*value),
-            SqlValue::Date(value) => query_as.bind(*value),
-            SqlValue::DateTime(value) => query_as.bind(*value),
-            SqlValue::Decimal(value) => query_as.bind(*value),
-            &SqlValue::Null => query_as.bind(None::<&str>),
+/// error when a primary key value doesn't have one value per primary key column.
+pub fn check_primary_key_arity(columns: usize, values: usize) -> Result<()> {
+    if columns == values {
+        Ok(())
+    } else {
+        Err(Error::InvalidArgument(format!(
+            "primary key has {columns} columns, got {values} values"
+        )))
+    }
+}
+
+/// `` `id` IN (?, ?) `` or `` (`a`, `b`) IN ((?, ?), (?, ?)) `` for `count` keys.
+pub fn primary_keys_in_sql(primary_keys: &[LambdaField], count: usize) -> String {
+    let columns = primary_keys
+        .iter()
+        .map(|primary_key| format!("`{primary_key}`"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    if primary_keys.len() == 1 {
+        let placeholders = vec!["?"; count].join(", ");
+        return format!("{columns} IN ({placeholders})");
+    }
+    let item = format!("({})", vec!["?"; primary_keys.len()].join(", "));
+    let placeholders = vec![item.as_str(); count].join(", ");
+    format!("({columns}) IN ({placeholders})")
+}
+
+/// deletes the rows of `keys`, one `Vec` of values per primary key, `BIND_CHUNK_SIZE` values per
+/// statement, returns the rows affected.
+pub async fn delete_in_primary_keys<E>(
+    conn: &mut MySqlConnection,
+    keys: &[Vec<SqlValue>],
+) -> Result<u64>
+where
+    E: Entity,
+{
+    let primary_keys = E::primary_keys();
+    let keys_per_statement = (BIND_CHUNK_SIZE / primary_keys.len()).max(1);
+    let mut rows = 0;
+    for chunk in keys.chunks(keys_per_statement) {
+        let sql = format!(
+            "DELETE FROM {} WHERE {}",
+            E::table_name(),
+            primary_keys_in_sql(&primary_keys, chunk.len())
+        );
+        let result = sqlx::query_with(&sql, arguments(&chunk.concat())?)
+            .execute(&mut *conn)
+            .await?;
+        rows += result.rows_affected();
+    }
+    Ok(rows)
+}
+
+/// indexes of the primary key columns in `E::columns()`.
+fn primary_key_indexes<E>() -> Result<Vec<usize>>
+where
+    E: Entity,
+{
+    let columns = E::columns();
+    E::primary_keys()
+        .iter()
+        .map(|primary_key| {
+            columns
+                .iter()
+                .position(|column| column.name == **primary_key)
+                .ok_or_else(|| {
+                    Error::InvalidArgument(format!(
+                        "primary key {primary_key} is not a column of {}",
+                        E::table_name()
+                    ))
+                })
+        })
+        .collect()
+}
+
+/// `` UPDATE t SET `a` = ? .. WHERE `id` = ? LIMIT 1 `` of `entity`, fields are set by their update
+/// strategy (`NotNull` when not declared), primary keys and skipped fields never.
+pub fn update_by_primary_key_sql<E>(entity: &E) -> Result<Statement>
+where
+    E: Entity,
+{
+    let key_indexes = primary_key_indexes::<E>()?;
+    let row = entity.values();
+    let mut sets = vec![];
+    let mut values = vec![];
+    for (index, (column, value)) in E::columns().iter().zip(&row).enumerate() {
+        if column.skip || key_indexes.contains(&index) {
+            continue;
+        }
+        if column
+            .update_strategy
+            .unwrap_or(UpdateStrategy::NotNull)
+            .should_set(value)
+        {
+            sets.push(format!("`{}` = ?", column.name));
+            values.push(value.clone());
         }
     }
-    query_as
+    if sets.is_empty() {
+        return Err(Error::InvalidArgument(format!(
+            "update of {} has no fields to set",
+            E::table_name()
+        )));
+    }
+    if key_indexes
+        .iter()
+        .any(|&index| matches!(row[index], SqlValue::Null))
+    {
+        return Err(Error::InvalidArgument(format!(
+            "update of {} needs the primary key",
+            E::table_name()
+        )));
+    }
+    let conditions = E::primary_keys()
+        .iter()
+        .map(|primary_key| format!("`{primary_key}` = ?"))
+        .collect::<Vec<_>>()
+        .join(" AND ");
+    values.extend(key_indexes.iter().map(|&index| row[index].clone()));
+    let sql = format!(
+        "UPDATE {} SET {} WHERE {conditions} LIMIT 1",
+        E::table_name(),
+        sets.join(", ")
+    );
+    Ok((sql, values))
 }
 
-pub fn bind_query_scalar<'q, T>(
-    mut query_scalar: QueryScalar<'q, MySql, T, MySqlArguments>,
-    values: &Vec<SqlValue>,
-) -> QueryScalar<'q, MySql, T, MySqlArguments> {
-    for value in values {
-        query_scalar = match value {
-            SqlValue::ISize(value) => query_scalar.bind(*value as i64),
-            SqlValue::USize(value) => query_scalar.bind(*value as u64),
-            SqlValue::I8(value) => query_scalar.bind(*value),
-            SqlValue::U8(value) => query_scalar.bind(*value),
-            SqlValue::I16(value) => query_scalar.bind(*value),
-            SqlValue::U16(value) => query_scalar.bind(*value),
-            SqlValue::I32(value) => query_scalar.bind(*value),
-            SqlValue::U32(value) => query_scalar.bind(*value),
-            SqlValue::I64(value) => query_scalar.bind(*value),
-            SqlValue::U64(value) => query_scalar.bind(*value),
-            SqlValue::F32(value) => query_scalar.bind(*value),
-            SqlValue::F64(value) => query_scalar.bind(*value),
-            SqlValue::Bool(value) => query_scalar.bind(*value),
-            SqlValue::Str(value) => query_scalar.bind(value.clone()),
-            SqlValue::Time(value) => query_scalar.bind(*value),
-            SqlValue::Date(value) => query_scalar.bind(*value),
-            SqlValue::DateTime(value) => query_scalar.bind(*value),
-            SqlValue::Decimal(value) => query_scalar.bind(*value),
-            &SqlValue::Null => query_scalar.bind(None::<&str>),
+/// updates every entity by its primary key, `chunk_size` entities per statement, returns the rows
+/// affected of every chunk.
+///
+/// every column is set by `` `a` = CASE `id` WHEN ? THEN ? .. ELSE `a` END ``, so `None` fields and fields
+/// skipped by their update strategy keep the column value.
+pub async fn update_batch_by_primary_key<E>(
+    conn: &mut MySqlConnection,
+    entities: &[E],
+    chunk_size: usize,
+) -> Result<Vec<u64>>
+where
+    E: Entity,
+{
+    let mut affected = vec![];
+    for statement in update_batch_sql(entities, chunk_size)? {
+        let Some((sql, values)) = statement else {
+            affected.push(0);
+            continue;
+        };
+        let result = sqlx::query_with(&sql, arguments(&values)?)
+            .execute(&mut *conn)
+            .await?;
+        affected.push(result.rows_affected());
+    }
+    Ok(affected)
+}
+
+/// statements of `update_batch_by_primary_key`, one per chunk, `None` when a chunk sets nothing.
+fn update_batch_sql<E>(entities: &[E], chunk_size: usize) -> Result<Vec<Option<Statement>>>
+where
+    E: Entity,
+{
+    let primary_keys = E::primary_keys();
+    let columns = E::columns();
+    let key_indexes = primary_key_indexes::<E>()?;
+    let set_indexes = columns
+        .iter()
+        .enumerate()
+        .filter(|(index, column)| {
+            !column.skip
+                && !key_indexes.contains(index)
+                && column.update_strategy != Some(UpdateStrategy::Never)
+        })
+        .map(|(index, _)| index)
+        .collect::<Vec<_>>();
+    let rows = entities.iter().map(Entity::values).collect::<Vec<_>>();
+    if rows.iter().any(|row| {
+        key_indexes
+            .iter()
+            .any(|&index| matches!(row[index], SqlValue::Null))
+    }) {
+        return Err(Error::InvalidArgument(format!(
+            "batch update of {} needs the primary key of every entity",
+            E::table_name()
+        )));
+    }
+    // WHEN `a` = ? AND `b` = ?, a single key uses CASE `id` WHEN ?
+    let when = primary_keys
+        .iter()
+        .map(|primary_key| format!("`{primary_key}` = ?"))
+        .collect::<Vec<_>>()
+        .join(" AND ");
+    let binds_per_row = set_indexes.len() * (key_indexes.len() + 1) + key_indexes.len();
+    let rows_per_statement = chunk_size.min(BIND_CHUNK_SIZE / binds_per_row).max(1);
+    let mut statements = vec![];
+    for chunk in rows.chunks(rows_per_statement) {
+        let mut sets = vec![];
+        let mut values = vec![];
+        for &index in &set_indexes {
+            let column = &columns[index];
+            let strategy = column.update_strategy.unwrap_or(UpdateStrategy::NotNull);
+            let mut cases = String::new();
+            for row in chunk.iter().filter(|row| strategy.should_set(&row[index])) {
+                if key_indexes.len() == 1 {
+                    cases += " WHEN ? THEN ?";
+                } else {
+                    cases += &format!(" WHEN {when} THEN ?");
+                }
+                values.extend(key_indexes.iter().map(|&key| row[key].clone()));
+                values.push(row[index].clone());
+            }
+            if cases.is_empty() {
+                continue;
+            }
+            let case = if key_indexes.len() == 1 {
+                format!("CASE `{}`{cases}", primary_keys[0])
+            } else {
+                format!("CASE{cases}")
+            };
+            sets.push(format!("`{0}` = {case} ELSE `{0}` END", column.name));
+        }
+        if sets.is_empty() {
+            statements.push(None);
+            continue;
+        }
+        for row in chunk {
+            values.extend(key_indexes.iter().map(|&key| row[key].clone()));
         }
+        let sql = format!(
+            "UPDATE {} SET {} WHERE {}",
+            E::table_name(),
+            sets.join(", "),
+            primary_keys_in_sql(&primary_keys, chunk.len())
+        );
+        statements.push(Some((sql, values)));
+    }
+    Ok(statements)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{User, UserRole};
+
+    #[test]
+    fn arguments_reject_too_many_values() {
+        assert!(arguments(&vec![SqlValue::U64(1); MAX_BIND_VALUES]).is_ok());
+        let err = arguments(&vec![SqlValue::U64(1); MAX_BIND_VALUES + 1]).unwrap_err();
+        assert!(matches!(err, Error::InvalidArgument(_)));
+    }
+
+    #[test]
+    fn primary_keys_in() {
+        let id = [LambdaField::new("ID")];
+        assert_eq!(primary_keys_in_sql(&id, 2), "`ID` IN (?, ?)");
+        let keys = [LambdaField::new("user_id"), LambdaField::new("role_id")];
+        assert_eq!(
+            primary_keys_in_sql(&keys, 2),
+            "(`user_id`, `role_id`) IN ((?, ?), (?, ?))"
+        );
+    }
+
+    #[test]
+    fn update_by_primary_key_follows_update_strategy() {
+        let user = User {
+            id: Some(1),
+            age: Some(18),
+            create_time: Some("2025-01-01".to_string()),
+            ..Default::default()
+        };
+        let (sql, values) = update_by_primary_key_sql(&user).unwrap();
+        assert_eq!(sql, "UPDATE user SET `age` = ? WHERE `ID` = ? LIMIT 1");
+        assert_eq!(format!("{values:?}"), "[I32(18), U64(1)]");
+
+        let no_key = User {
+            age: Some(18),
+            ..Default::default()
+        };
+        let err = update_by_primary_key_sql(&no_key).unwrap_err();
+        assert!(matches!(err, Error::InvalidArgument(_)));
+        let keys_only = UserRole {
+            user_id: Some(1),
+            role_id: Some(2),
+        };
+        let err = update_by_primary_key_sql(&keys_only).unwrap_err();
+        assert!(matches!(err, Error::InvalidArgument(_)));
+    }
+
+    #[test]
+    fn update_batch_uses_case_per_column() {
+        let users = [
+            User {
+                id: Some(1),
+                username: Some("a".to_string()),
+                age: Some(18),
+                ..Default::default()
+            },
+            User {
+                id: Some(2),
+                age: Some(20),
+                create_time: Some("2025-01-01".to_string()),
+                ..Default::default()
+            },
+            User {
+                id: Some(3),
+                ..Default::default()
+            },
+        ];
+        let statements = update_batch_sql(&users, 2).unwrap();
+        assert_eq!(statements.len(), 2);
+        let (sql, values) = statements[0].as_ref().unwrap();
+        assert_eq!(
+            sql,
+            "UPDATE user SET `username` = CASE `ID` WHEN ? THEN ? ELSE `username` END, \
+             `age` = CASE `ID` WHEN ? THEN ? WHEN ? THEN ? ELSE `age` END WHERE `ID` IN (?, ?)"
+        );
+        assert_eq!(
+            format!("{values:?}"),
+            r#"[U64(1), Str("a"), U64(1), I32(18), U64(2), I32(20), U64(1), U64(2)]"#
+        );
+        // nothing to set for the last user
+        assert!(statements[1].is_none());
+    }
+
+    #[test]
+    fn update_batch_needs_every_primary_key() {
+        let roles = [UserRole {
+            user_id: Some(1),
+            role_id: None,
+        }];
+        let err = update_batch_sql(&roles, 10).unwrap_err();
+        assert!(matches!(err, Error::InvalidArgument(_)));
     }
-    query_scalar
 }
@@ -1,17 +1,25 @@
-use crate::{
-    Entity, LambdaField,
-    repository::{bind_query, bind_query_as, bind_query_scalar},
-};
+use crate::expr::{Expr, MatchMode};
+use crate::repository::BIND_CHUNK_SIZE;
+use crate::{FieldValue, IntoField, IntoFields};
 use rust_decimal::Decimal;
-use sqlx::MySql;
-use sqlx::mysql::MySqlArguments;
-use sqlx::query::{Query, QueryAs, QueryScalar};
-use sqlx::types::chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use serde::Serialize;
+use sqlx::encode::IsNull;
+use sqlx::error::BoxDynError;
+use sqlx::mysql::MySqlTypeInfo;
+use sqlx::types::Json;
+use sqlx::types::chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use sqlx::{Encode, MySql, Type, TypeInfo};
+use std::borrow::Cow;
 use std::cell::RefCell;
 use std::cmp::PartialEq;
 use std::collections::{HashMap, HashSet};
+use std::fmt::Debug;
+use std::sync::Arc;
+use uuid::Uuid;
 
 pub trait Wrapper<'a> {
+    type Entity;
+
     fn wheres(&self) -> &Vec<Where<'a>>;
     fn wheres_push(&mut self, r#where: Where<'a>);
     fn or_index(&self) -> &HashSet<usize>;
@@ -21,17 +29,17 @@ pub trait Wrapper<'a> {
 
     fn eq<F, V>(self, field_func: F, value: V) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
-        self.eq_field(*field_func(), value)
+        self.eq_field(field_func.into_field(), value)
     }
 
     fn eq_flag<F, V>(mut self, field_func: F, value: V, flag: bool) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
         if flag {
@@ -42,20 +50,20 @@ pub trait Wrapper<'a> {
 
     fn eq_opt<F, V>(mut self, field_func: F, value: Option<V>) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
         if let Some(value) = value {
-            self = self.eq_field(*field_func(), value)
+            self = self.eq_field(field_func.into_field(), value)
         }
         self
     }
 
     fn eq_opt_flag<F, V>(mut self, field_func: F, value: Option<V>, flag: bool) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
         if flag {
@@ -64,6 +72,7 @@ pub trait Wrapper<'a> {
         self
     }
 
+    /// `SqlValue::Null` renders `IS NULL`.
     fn eq_field<V>(mut self, field: &'a str, value: V) -> Self
     where
         V: Into<SqlValue> + Clone,
@@ -73,7 +82,7 @@ pub trait Wrapper<'a> {
         self
     }
 
-    fn eq_field_flag<F, V>(mut self, field: &'a str, value: V, flag: bool) -> Self
+    fn eq_field_flag<V>(mut self, field: &'a str, value: V, flag: bool) -> Self
     where
         V: Into<SqlValue> + Clone,
         Self: Sized,
@@ -95,7 +104,7 @@ pub trait Wrapper<'a> {
         self
     }
 
-    fn eq_field_opt_flag<F, V>(mut self, field: &'a str, value: Option<V>, flag: bool) -> Self
+    fn eq_field_opt_flag<V>(mut self, field: &'a str, value: Option<V>, flag: bool) -> Self
     where
         V: Into<SqlValue> + Clone,
         Self: Sized,
@@ -106,20 +115,117 @@ pub trait Wrapper<'a> {
         self
     }
 
-    fn ne<F, V>(self, field_func: F, value: V) -> Self
+    /// null-safe equal `<=>`, `NULL <=> NULL` is true.
+    fn eq_null_safe<F, V>(self, field_func: F, value: V) -> Self
+    where
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        self.eq_null_safe_field(field_func.into_field(), value)
+    }
+
+    fn eq_null_safe_flag<F, V>(mut self, field_func: F, value: V, flag: bool) -> Self
+    where
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        if flag {
+            self = self.eq_null_safe(field_func, value);
+        }
+        self
+    }
+
+    fn eq_null_safe_opt<F, V>(mut self, field_func: F, value: Option<V>) -> Self
+    where
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        if let Some(value) = value {
+            self = self.eq_null_safe_field(field_func.into_field(), value);
+        }
+        self
+    }
+
+    fn eq_null_safe_opt_flag<F, V>(mut self, field_func: F, value: Option<V>, flag: bool) -> Self
+    where
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        if flag {
+            self = self.eq_null_safe_opt(field_func, value);
+        }
+        self
+    }
+
+    fn eq_null_safe_field<V>(mut self, field: &'a str, value: V) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
         V: Into<SqlValue> + Clone,
         Self: Sized,
     {
-        self.ne_field(*field_func(), value)
+        self.wheres_push(Where::new(
+            field,
+            Relationship::NullSafeEq,
+            vec![value.into()],
+        ));
+        self
     }
 
-    fn ne_flag<F, V>(mut self, field_func: F, value: V, flag: bool) -> Self
+    fn eq_null_safe_field_flag<V>(mut self, field: &'a str, value: V, flag: bool) -> Self
+    where
+        V: Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        if flag {
+            self = self.eq_null_safe_field(field, value);
+        }
+        self
+    }
+
+    fn eq_null_safe_field_opt<V>(mut self, field: &'a str, value: Option<V>) -> Self
+    where
+        V: Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        if let Some(value) = value {
+            self = self.eq_null_safe_field(field, value);
+        }
+        self
+    }
+
+    fn eq_null_safe_field_opt_flag<V>(
+        mut self,
+        field: &'a str,
+        value: Option<V>,
+        flag: bool,
+    ) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
         V: Into<SqlValue> + Clone,
         Self: Sized,
+    {
+        if flag {
+            self = self.eq_null_safe_field_opt(field, value);
+        }
+        self
+    }
+
+    fn ne<F, V>(self, field_func: F, value: V) -> Self
+    where
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        self.ne_field(field_func.into_field(), value)
+    }
+
+    fn ne_flag<F, V>(mut self, field_func: F, value: V, flag: bool) -> Self
+    where
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
+        Self: Sized,
     {
         if flag {
             self = self.ne(field_func, value);
@@ -129,20 +235,20 @@ pub trait Wrapper<'a> {
 
     fn ne_opt<F, V>(mut self, field_func: F, value: Option<V>) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
         if let Some(value) = value {
-            self = self.ne_field(*field_func(), value);
+            self = self.ne_field(field_func.into_field(), value);
         }
         self
     }
 
     fn ne_opt_flag<F, V>(mut self, field_func: F, value: Option<V>, flag: bool) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
         if flag {
@@ -151,6 +257,7 @@ pub trait Wrapper<'a> {
         self
     }
 
+    /// `SqlValue::Null` renders `IS NOT NULL`.
     fn ne_field<V>(mut self, field: &'a str, value: V) -> Self
     where
         V: Into<SqlValue> + Clone,
@@ -195,17 +302,17 @@ pub trait Wrapper<'a> {
 
     fn gt<F, V>(self, field_func: F, value: V) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
-        self.gt_field(*field_func(), value)
+        self.gt_field(field_func.into_field(), value)
     }
 
     fn gt_flag<F, V>(mut self, field_func: F, value: V, flag: bool) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
         if flag {
@@ -216,20 +323,20 @@ pub trait Wrapper<'a> {
 
     fn gt_opt<F, V>(mut self, field_func: F, value: Option<V>) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
         if let Some(value) = value {
-            self = self.gt_field(*field_func(), value);
+            self = self.gt_field(field_func.into_field(), value);
         }
         self
     }
 
     fn gt_opt_flag<F, V>(mut self, field_func: F, value: Option<V>, flag: bool) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
         if flag {
@@ -282,17 +389,17 @@ pub trait Wrapper<'a> {
 
     fn ge<F, V>(self, field_func: F, value: V) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
-        self.ge_field(*field_func(), value)
+        self.ge_field(field_func.into_field(), value)
     }
 
     fn ge_flag<F, V>(mut self, field_func: F, value: V, flag: bool) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
         if flag {
@@ -303,20 +410,20 @@ pub trait Wrapper<'a> {
 
     fn ge_opt<F, V>(mut self, field_func: F, value: Option<V>) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
         if let Some(value) = value {
-            self = self.ge_field(*field_func(), value);
+            self = self.ge_field(field_func.into_field(), value);
         }
         self
     }
 
     fn ge_opt_flag<F, V>(mut self, field_func: F, value: Option<V>, flag: bool) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
         if flag {
@@ -369,17 +476,17 @@ pub trait Wrapper<'a> {
 
     fn lt<F, V>(self, field_func: F, value: V) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
-        self.lt_field(*field_func(), value)
+        self.lt_field(field_func.into_field(), value)
     }
 
     fn lt_flag<F, V>(mut self, field_func: F, value: V, flag: bool) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
         if flag {
@@ -390,20 +497,20 @@ pub trait Wrapper<'a> {
 
     fn lt_opt<F, V>(mut self, field_func: F, value: Option<V>) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
         if let Some(value) = value {
-            self = self.lt_field(*field_func(), value);
+            self = self.lt_field(field_func.into_field(), value);
         }
         self
     }
 
     fn lt_opt_flag<F, V>(mut self, field_func: F, value: Option<V>, flag: bool) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
         if flag {
@@ -456,17 +563,17 @@ pub trait Wrapper<'a> {
 
     fn le<F, V>(self, field_func: F, value: V) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
-        self.le_field(*field_func(), value)
+        self.le_field(field_func.into_field(), value)
     }
 
     fn le_flag<F, V>(mut self, field_func: F, value: V, flag: bool) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
         if flag {
@@ -477,20 +584,20 @@ pub trait Wrapper<'a> {
 
     fn le_opt<F, V>(mut self, field_func: F, value: Option<V>) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
         if let Some(value) = value {
-            self = self.le_field(*field_func(), value);
+            self = self.le_field(field_func.into_field(), value);
         }
         self
     }
 
     fn le_opt_flag<F, V>(mut self, field_func: F, value: Option<V>, flag: bool) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
         if flag {
@@ -543,11 +650,11 @@ pub trait Wrapper<'a> {
 
     fn between<F, V>(self, field_func: F, value_left: V, value_right: V) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
-        self.between_field(*field_func(), value_left, value_right)
+        self.between_field(field_func.into_field(), value_left, value_right)
     }
 
     fn between_flag<F, V>(
@@ -558,8 +665,8 @@ pub trait Wrapper<'a> {
         flag: bool,
     ) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
         if flag {
@@ -575,14 +682,14 @@ pub trait Wrapper<'a> {
         value_right: Option<V>,
     ) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
         if let Some(value_left) = value_left
             && let Some(value_right) = value_right
         {
-            self = self.between_field(*field_func(), value_left, value_right);
+            self = self.between_field(field_func.into_field(), value_left, value_right);
         }
         self
     }
@@ -595,8 +702,8 @@ pub trait Wrapper<'a> {
         flag: bool,
     ) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
         if flag {
@@ -672,11 +779,11 @@ pub trait Wrapper<'a> {
 
     fn not_between<F, V>(self, field_func: F, value_left: V, value_right: V) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
-        self.not_between_field(*field_func(), value_left, value_right)
+        self.not_between_field(field_func.into_field(), value_left, value_right)
     }
 
     fn not_between_flag<F, V>(
@@ -687,8 +794,8 @@ pub trait Wrapper<'a> {
         flag: bool,
     ) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
         if flag {
@@ -704,14 +811,14 @@ pub trait Wrapper<'a> {
         value_right: Option<V>,
     ) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
         if let Some(value_left) = value_left
             && let Some(value_right) = value_right
         {
-            self = self.not_between_field(*field_func(), value_left, value_right);
+            self = self.not_between_field(field_func.into_field(), value_left, value_right);
         }
         self
     }
@@ -724,8 +831,8 @@ pub trait Wrapper<'a> {
         flag: bool,
     ) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
         if flag {
@@ -801,17 +908,17 @@ pub trait Wrapper<'a> {
 
     fn like<F, V>(self, field_func: F, value: V) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
-        self.like_field(*field_func(), value)
+        self.like_field(field_func.into_field(), value)
     }
 
     fn like_flag<F, V>(mut self, field_func: F, value: V, flag: bool) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
         if flag {
@@ -822,20 +929,20 @@ pub trait Wrapper<'a> {
 
     fn like_opt<F, V>(mut self, field_func: F, value: Option<V>) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
         if let Some(value) = value {
-            self = self.like_field(*field_func(), value);
+            self = self.like_field(field_func.into_field(), value);
         }
         self
     }
 
     fn like_opt_flag<F, V>(mut self, field_func: F, value: Option<V>, flag: bool) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
         if flag {
@@ -888,17 +995,17 @@ pub trait Wrapper<'a> {
 
     fn not_like<F, V>(self, field_func: F, value: V) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
-        self.not_like_field(*field_func(), value)
+        self.not_like_field(field_func.into_field(), value)
     }
 
     fn not_like_flag<F, V>(mut self, field_func: F, value: V, flag: bool) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
         if flag {
@@ -909,20 +1016,20 @@ pub trait Wrapper<'a> {
 
     fn not_like_opt<F, V>(mut self, field_func: F, value: Option<V>) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
         if let Some(value) = value {
-            self = self.not_like_field(*field_func(), value);
+            self = self.not_like_field(field_func.into_field(), value);
         }
         self
     }
 
     fn not_like_opt_flag<F, V>(mut self, field_func: F, value: Option<V>, flag: bool) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
         if flag {
@@ -975,17 +1082,17 @@ pub trait Wrapper<'a> {
 
     fn like_left<F, V>(self, field_func: F, value: V) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
-        self.like_left_field(*field_func(), value)
+        self.like_left_field(field_func.into_field(), value)
     }
 
     fn like_left_flag<F, V>(mut self, field_func: F, value: V, flag: bool) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
         if flag {
@@ -996,20 +1103,20 @@ pub trait Wrapper<'a> {
 
     fn like_left_pot<F, V>(mut self, field_func: F, value: Option<V>) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
         if let Some(value) = value {
-            self = self.like_left_field(*field_func(), value);
+            self = self.like_left_field(field_func.into_field(), value);
         }
         self
     }
 
     fn like_left_pot_flag<F, V>(mut self, field_func: F, value: Option<V>, flag: bool) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
         if flag {
@@ -1066,17 +1173,17 @@ pub trait Wrapper<'a> {
 
     fn like_right<F, V>(self, field_func: F, value: V) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
-        self.like_right_field(*field_func(), value)
+        self.like_right_field(field_func.into_field(), value)
     }
 
     fn like_right_flag<F, V>(mut self, field_func: F, value: V, flag: bool) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
         if flag {
@@ -1087,20 +1194,20 @@ pub trait Wrapper<'a> {
 
     fn like_right_opt<F, V>(mut self, field_func: F, value: Option<V>) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
         if let Some(value) = value {
-            self = self.like_right_field(*field_func(), value);
+            self = self.like_right_field(field_func.into_field(), value);
         }
         self
     }
 
     fn like_right_opt_flag<F, V>(mut self, field_func: F, value: Option<V>, flag: bool) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
         if flag {
@@ -1155,186 +1262,177 @@ pub trait Wrapper<'a> {
         self
     }
 
-    fn r#in<F, V>(self, field_func: F, values: HashSet<V>) -> Self
+    fn like_raw<F, V>(self, field_func: F, value: V) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
-        self.in_field(*field_func(), values)
+        self.like_raw_field(field_func.into_field(), value)
     }
 
-    fn in_flag<F, V>(mut self, field_func: F, values: HashSet<V>, flag: bool) -> Self
+    fn like_raw_flag<F, V>(mut self, field_func: F, value: V, flag: bool) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
         if flag {
-            self = self.r#in(field_func, values);
+            self = self.like_raw(field_func, value);
         }
         self
     }
 
-    fn in_opt<F, V>(mut self, field_func: F, values: Option<HashSet<V>>) -> Self
+    fn like_raw_opt<F, V>(mut self, field_func: F, value: Option<V>) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
-        if let Some(values) = values {
-            self = self.in_field(*field_func(), values);
+        if let Some(value) = value {
+            self = self.like_raw_field(field_func.into_field(), value);
         }
         self
     }
 
-    fn in_opt_flag<F, V>(mut self, field_func: F, values: Option<HashSet<V>>, flag: bool) -> Self
+    fn like_raw_opt_flag<F, V>(mut self, field_func: F, value: Option<V>, flag: bool) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
         if flag {
-            self = self.in_opt(field_func, values);
+            self = self.like_raw_opt(field_func, value);
         }
         self
     }
 
-    fn in_field<V>(mut self, field: &'a str, values: HashSet<V>) -> Self
+    fn like_raw_field<V>(mut self, field: &'a str, value: V) -> Self
     where
         V: Into<SqlValue> + Clone,
         Self: Sized,
     {
-        self.wheres_push(Where::new(
-            field,
-            Relationship::In,
-            values.iter().map(|v| v.clone().into()).collect::<Vec<_>>(),
-        ));
+        self.wheres_push(Where::new(field, Relationship::LikeRaw, vec![value.into()]));
         self
     }
 
-    fn in_field_flag<V>(mut self, field: &'a str, values: HashSet<V>, flag: bool) -> Self
+    fn like_raw_field_flag<V>(mut self, field: &'a str, value: V, flag: bool) -> Self
     where
         V: Into<SqlValue> + Clone,
         Self: Sized,
     {
         if flag {
-            self = self.in_field(field, values);
+            self = self.like_raw_field(field, value);
         }
         self
     }
 
-    fn in_field_opt<V>(mut self, field: &'a str, values: Option<HashSet<V>>) -> Self
+    fn like_raw_field_opt<V>(mut self, field: &'a str, value: Option<V>) -> Self
     where
         V: Into<SqlValue> + Clone,
         Self: Sized,
     {
-        if let Some(values) = values {
-            self = self.in_field(field, values);
+        if let Some(value) = value {
+            self = self.like_raw_field(field, value);
         }
         self
     }
 
-    fn in_field_opt_flag<V>(
-        mut self,
-        field: &'a str,
-        values: Option<HashSet<V>>,
-        flag: bool,
-    ) -> Self
+    fn like_raw_field_opt_flag<V>(mut self, field: &'a str, value: Option<V>, flag: bool) -> Self
     where
         V: Into<SqlValue> + Clone,
         Self: Sized,
     {
         if flag {
-            self = self.in_field_opt(field, values);
+            self = self.like_raw_field_opt(field, value);
         }
         self
     }
 
-    fn in_vec<F, V>(self, field_func: F, values: Vec<V>) -> Self
+    fn not_like_raw<F, V>(self, field_func: F, value: V) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
-        self.in_vec_field(*field_func(), values)
+        self.not_like_raw_field(field_func.into_field(), value)
     }
 
-    fn in_vec_flag<F, V>(mut self, field_func: F, values: Vec<V>, flag: bool) -> Self
+    fn not_like_raw_flag<F, V>(mut self, field_func: F, value: V, flag: bool) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
         if flag {
-            self = self.in_vec(field_func, values);
+            self = self.not_like_raw(field_func, value);
         }
         self
     }
 
-    fn in_vec_opt<F, V>(mut self, field_func: F, values: Option<Vec<V>>) -> Self
+    fn not_like_raw_opt<F, V>(mut self, field_func: F, value: Option<V>) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
-        if let Some(values) = values {
-            self = self.in_vec_field(*field_func(), values);
+        if let Some(value) = value {
+            self = self.not_like_raw_field(field_func.into_field(), value);
         }
         self
     }
 
-    fn in_vec_opt_flag<F, V>(mut self, field_func: F, values: Option<Vec<V>>, flag: bool) -> Self
+    fn not_like_raw_opt_flag<F, V>(mut self, field_func: F, value: Option<V>, flag: bool) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
         if flag {
-            self = self.in_vec_opt(field_func, values);
+            self = self.not_like_raw_opt(field_func, value);
         }
         self
     }
 
-    fn in_vec_field<V>(mut self, field: &'a str, values: Vec<V>) -> Self
+    fn not_like_raw_field<V>(mut self, field: &'a str, value: V) -> Self
     where
         V: Into<SqlValue> + Clone,
         Self: Sized,
     {
         self.wheres_push(Where::new(
             field,
-            Relationship::In,
-            values.iter().map(|v| v.clone().into()).collect::<Vec<_>>(),
+            Relationship::NotLikeRaw,
+            vec![value.into()],
         ));
         self
     }
 
-    fn in_vec_field_flag<V>(mut self, field: &'a str, values: Vec<V>, flag: bool) -> Self
+    fn not_like_raw_field_flag<V>(mut self, field: &'a str, value: V, flag: bool) -> Self
     where
         V: Into<SqlValue> + Clone,
         Self: Sized,
     {
         if flag {
-            self = self.in_vec_field(field, values);
+            self = self.not_like_raw_field(field, value);
         }
         self
     }
 
-    fn in_vec_field_opt<V>(mut self, field: &'a str, values: Option<Vec<V>>) -> Self
+    fn not_like_raw_field_opt<V>(mut self, field: &'a str, value: Option<V>) -> Self
     where
         V: Into<SqlValue> + Clone,
         Self: Sized,
     {
-        if let Some(values) = values {
-            self = self.in_vec_field(field, values);
+        if let Some(value) = value {
+            self = self.not_like_raw_field(field, value);
         }
         self
     }
 
-    fn in_vec_field_opt_flag<V>(
+    fn not_like_raw_field_opt_flag<V>(
         mut self,
         field: &'a str,
-        values: Option<Vec<V>>,
+        value: Option<V>,
         flag: bool,
     ) -> Self
     where
@@ -1342,97 +1440,95 @@ pub trait Wrapper<'a> {
         Self: Sized,
     {
         if flag {
-            self = self.in_vec_field_opt(field, values);
+            self = self.not_like_raw_field_opt(field, value);
         }
         self
     }
 
-    fn not_in<F, V>(self, field_func: F, values: HashSet<V>) -> Self
+    /// the list must fit one statement (`repository::MAX_BIND_VALUES`), except on update and delete
+    /// without LIMIT, OR or brackets, where a list over `repository::BIND_CHUNK_SIZE` values runs in
+    /// several statements in one transaction.
+    fn r#in<F, V>(self, field_func: F, values: HashSet<V>) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
-        self.not_in_field(*field_func(), values)
+        self.in_field(field_func.into_field(), values)
     }
 
-    fn not_in_flag<F, V>(mut self, field_func: F, values: HashSet<V>, flag: bool) -> Self
+    fn in_flag<F, V>(mut self, field_func: F, values: HashSet<V>, flag: bool) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
         if flag {
-            self = self.not_in(field_func, values);
+            self = self.r#in(field_func, values);
         }
         self
     }
 
-    fn not_in_opt<F, V>(mut self, field_func: F, values: Option<HashSet<V>>) -> Self
+    fn in_opt<F, V>(mut self, field_func: F, values: Option<HashSet<V>>) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
         if let Some(values) = values {
-            self = self.not_in_field(*field_func(), values);
+            self = self.in_field(field_func.into_field(), values);
         }
         self
     }
 
-    fn not_in_opt_flag<F, V>(
-        mut self,
-        field_func: F,
-        values: Option<HashSet<V>>,
-        flag: bool,
-    ) -> Self
+    fn in_opt_flag<F, V>(mut self, field_func: F, values: Option<HashSet<V>>, flag: bool) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
         if flag {
-            self = self.not_in_opt(field_func, values);
+            self = self.in_opt(field_func, values);
         }
         self
     }
 
-    fn not_in_field<V>(mut self, field: &'a str, values: HashSet<V>) -> Self
+    fn in_field<V>(mut self, field: &'a str, values: HashSet<V>) -> Self
     where
         V: Into<SqlValue> + Clone,
         Self: Sized,
     {
         self.wheres_push(Where::new(
             field,
-            Relationship::NotIn,
+            Relationship::In,
             values.iter().map(|v| v.clone().into()).collect::<Vec<_>>(),
         ));
         self
     }
 
-    fn not_in_field_flag<V>(mut self, field: &'a str, values: HashSet<V>, flag: bool) -> Self
+    fn in_field_flag<V>(mut self, field: &'a str, values: HashSet<V>, flag: bool) -> Self
     where
         V: Into<SqlValue> + Clone,
         Self: Sized,
     {
         if flag {
-            self = self.not_in_field(field, values);
+            self = self.in_field(field, values);
         }
         self
     }
 
-    fn not_in_field_opt<V>(mut self, field: &'a str, values: Option<HashSet<V>>) -> Self
+    fn in_field_opt<V>(mut self, field: &'a str, values: Option<HashSet<V>>) -> Self
     where
         V: Into<SqlValue> + Clone,
         Self: Sized,
     {
         if let Some(values) = values {
-            self = self.not_in_field(field, values);
+            self = self.in_field(field, values);
         }
         self
     }
 
-    fn not_in_opt_field_flag<V>(
+    fn in_field_opt_flag<V>(
         mut self,
         field: &'a str,
         values: Option<HashSet<V>>,
@@ -1443,97 +1539,92 @@ pub trait Wrapper<'a> {
         Self: Sized,
     {
         if flag {
-            self = self.not_in_field_opt(field, values);
+            self = self.in_field_opt(field, values);
         }
         self
     }
 
-    fn not_in_vec<F, V>(self, field_func: F, values: Vec<V>) -> Self
+    fn in_vec<F, V>(self, field_func: F, values: Vec<V>) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
-        self.not_in_vec_field(*field_func(), values)
+        self.in_vec_field(field_func.into_field(), values)
     }
 
-    fn not_in_vec_flag<F, V>(mut self, field_func: F, values: Vec<V>, flag: bool) -> Self
+    fn in_vec_flag<F, V>(mut self, field_func: F, values: Vec<V>, flag: bool) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
         if flag {
-            self = self.not_in_vec(field_func, values);
+            self = self.in_vec(field_func, values);
         }
         self
     }
 
-    fn not_in_vec_opt<F, V>(mut self, field_func: F, values: Option<Vec<V>>) -> Self
+    fn in_vec_opt<F, V>(mut self, field_func: F, values: Option<Vec<V>>) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
         if let Some(values) = values {
-            self = self.not_in_vec_field(*field_func(), values);
+            self = self.in_vec_field(field_func.into_field(), values);
         }
         self
     }
 
-    fn not_in_vec_opt_flag<F, V>(
-        mut self,
-        field_func: F,
-        values: Option<Vec<V>>,
-        flag: bool,
-    ) -> Self
+    fn in_vec_opt_flag<F, V>(mut self, field_func: F, values: Option<Vec<V>>, flag: bool) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
         if flag {
-            self = self.not_in_vec_opt(field_func, values);
+            self = self.in_vec_opt(field_func, values);
         }
         self
     }
 
-    fn not_in_vec_field<V>(mut self, field: &'a str, values: Vec<V>) -> Self
+    fn in_vec_field<V>(mut self, field: &'a str, values: Vec<V>) -> Self
     where
         V: Into<SqlValue> + Clone,
         Self: Sized,
     {
         self.wheres_push(Where::new(
             field,
-            Relationship::NotIn,
+            Relationship::In,
             values.iter().map(|v| v.clone().into()).collect::<Vec<_>>(),
         ));
         self
     }
 
-    fn not_in_vec_field_flag<V>(mut self, field: &'a str, values: Vec<V>, flag: bool) -> Self
+    fn in_vec_field_flag<V>(mut self, field: &'a str, values: Vec<V>, flag: bool) -> Self
     where
         V: Into<SqlValue> + Clone,
         Self: Sized,
     {
         if flag {
-            self = self.not_in_vec_field(field, values);
+            self = self.in_vec_field(field, values);
         }
         self
     }
 
-    fn not_in_vec_field_opt<V>(mut self, field: &'a str, values: Option<Vec<V>>) -> Self
+    fn in_vec_field_opt<V>(mut self, field: &'a str, values: Option<Vec<V>>) -> Self
     where
         V: Into<SqlValue> + Clone,
         Self: Sized,
     {
         if let Some(values) = values {
-            self = self.not_in_vec_field(field, values);
+            self = self.in_vec_field(field, values);
         }
         self
     }
 
-    fn not_in_vec_field_opt_flag<V>(
+    fn in_vec_field_opt_flag<V>(
         mut self,
         field: &'a str,
         values: Option<Vec<V>>,
@@ -1544,89 +1635,1132 @@ pub trait Wrapper<'a> {
         Self: Sized,
     {
         if flag {
-            self = self.not_in_vec_field_opt(field, values);
+            self = self.in_vec_field_opt(field, values);
         }
         self
     }
 
-    fn null<F, V>(self, field_func: F) -> Self
+    /// the list must fit one statement (`repository::MAX_BIND_VALUES`).
+    fn not_in<F, V>(self, field_func: F, values: HashSet<V>) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
-        self.null_field::<V>(*field_func())
+        self.not_in_field(field_func.into_field(), values)
     }
 
-    fn null_flag<F, V>(mut self, field_func: F, flag: bool) -> Self
+    fn not_in_flag<F, V>(mut self, field_func: F, values: HashSet<V>, flag: bool) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
-        V: Into<SqlValue> + Clone,
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
         Self: Sized,
     {
         if flag {
-            self = self.null::<F, V>(field_func);
+            self = self.not_in(field_func, values);
         }
         self
     }
 
-    fn null_field<V>(mut self, field: &'a str) -> Self
+    fn not_in_opt<F, V>(mut self, field_func: F, values: Option<HashSet<V>>) -> Self
     where
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        if let Some(values) = values {
+            self = self.not_in_field(field_func.into_field(), values);
+        }
+        self
+    }
+
+    fn not_in_opt_flag<F, V>(
+        mut self,
+        field_func: F,
+        values: Option<HashSet<V>>,
+        flag: bool,
+    ) -> Self
+    where
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        if flag {
+            self = self.not_in_opt(field_func, values);
+        }
+        self
+    }
+
+    fn not_in_field<V>(mut self, field: &'a str, values: HashSet<V>) -> Self
+    where
+        V: Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        self.wheres_push(Where::new(
+            field,
+            Relationship::NotIn,
+            values.iter().map(|v| v.clone().into()).collect::<Vec<_>>(),
+        ));
+        self
+    }
+
+    fn not_in_field_flag<V>(mut self, field: &'a str, values: HashSet<V>, flag: bool) -> Self
+    where
+        V: Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        if flag {
+            self = self.not_in_field(field, values);
+        }
+        self
+    }
+
+    fn not_in_field_opt<V>(mut self, field: &'a str, values: Option<HashSet<V>>) -> Self
+    where
+        V: Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        if let Some(values) = values {
+            self = self.not_in_field(field, values);
+        }
+        self
+    }
+
+    fn not_in_opt_field_flag<V>(
+        mut self,
+        field: &'a str,
+        values: Option<HashSet<V>>,
+        flag: bool,
+    ) -> Self
+    where
+        V: Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        if flag {
+            self = self.not_in_field_opt(field, values);
+        }
+        self
+    }
+
+    fn not_in_vec<F, V>(self, field_func: F, values: Vec<V>) -> Self
+    where
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        self.not_in_vec_field(field_func.into_field(), values)
+    }
+
+    fn not_in_vec_flag<F, V>(mut self, field_func: F, values: Vec<V>, flag: bool) -> Self
+    where
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        if flag {
+            self = self.not_in_vec(field_func, values);
+        }
+        self
+    }
+
+    fn not_in_vec_opt<F, V>(mut self, field_func: F, values: Option<Vec<V>>) -> Self
+    where
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        if let Some(values) = values {
+            self = self.not_in_vec_field(field_func.into_field(), values);
+        }
+        self
+    }
+
+    fn not_in_vec_opt_flag<F, V>(
+        mut self,
+        field_func: F,
+        values: Option<Vec<V>>,
+        flag: bool,
+    ) -> Self
+    where
+        F: IntoField<'a, Self::Entity>,
+        V: FieldValue<F::Value> + Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        if flag {
+            self = self.not_in_vec_opt(field_func, values);
+        }
+        self
+    }
+
+    fn not_in_vec_field<V>(mut self, field: &'a str, values: Vec<V>) -> Self
+    where
+        V: Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        self.wheres_push(Where::new(
+            field,
+            Relationship::NotIn,
+            values.iter().map(|v| v.clone().into()).collect::<Vec<_>>(),
+        ));
+        self
+    }
+
+    fn not_in_vec_field_flag<V>(mut self, field: &'a str, values: Vec<V>, flag: bool) -> Self
+    where
+        V: Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        if flag {
+            self = self.not_in_vec_field(field, values);
+        }
+        self
+    }
+
+    fn not_in_vec_field_opt<V>(mut self, field: &'a str, values: Option<Vec<V>>) -> Self
+    where
+        V: Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        if let Some(values) = values {
+            self = self.not_in_vec_field(field, values);
+        }
+        self
+    }
+
+    fn not_in_vec_field_opt_flag<V>(
+        mut self,
+        field: &'a str,
+        values: Option<Vec<V>>,
+        flag: bool,
+    ) -> Self
+    where
+        V: Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        if flag {
+            self = self.not_in_vec_field_opt(field, values);
+        }
+        self
+    }
+
+    /// tuple IN, e.g. `.in_tuple((UserRole::user_id_field, UserRole::role_id_field), vec![(1, 2)])`.
+    ///
+    /// a tuple without one value per field fails with `InvalidArgument` when the statement runs.
+    fn in_tuple<F, K>(self, fields: F, values: Vec<K>) -> Self
+    where
+        F: IntoFields<'a, Self::Entity>,
+        K: SqlTuple,
+        Self: Sized,
+    {
+        self.in_tuple_field(fields.into_fields(), values)
+    }
+
+    fn in_tuple_flag<F, K>(mut self, fields: F, values: Vec<K>, flag: bool) -> Self
+    where
+        F: IntoFields<'a, Self::Entity>,
+        K: SqlTuple,
+        Self: Sized,
+    {
+        if flag {
+            self = self.in_tuple(fields, values);
+        }
+        self
+    }
+
+    fn in_tuple_opt<F, K>(mut self, fields: F, values: Option<Vec<K>>) -> Self
+    where
+        F: IntoFields<'a, Self::Entity>,
+        K: SqlTuple,
+        Self: Sized,
+    {
+        if let Some(values) = values {
+            self = self.in_tuple(fields, values);
+        }
+        self
+    }
+
+    fn in_tuple_opt_flag<F, K>(mut self, fields: F, values: Option<Vec<K>>, flag: bool) -> Self
+    where
+        F: IntoFields<'a, Self::Entity>,
+        K: SqlTuple,
+        Self: Sized,
+    {
+        if flag {
+            self = self.in_tuple_opt(fields, values);
+        }
+        self
+    }
+
+    fn in_tuple_field<K>(mut self, fields: Vec<&'a str>, values: Vec<K>) -> Self
+    where
+        K: SqlTuple,
+        Self: Sized,
+    {
+        self.wheres_push(Where::tuple(
+            fields,
+            Relationship::In,
+            values
+                .into_iter()
+                .map(|v| v.into_values())
+                .collect::<Vec<_>>(),
+        ));
+        self
+    }
+
+    fn in_tuple_field_flag<K>(mut self, fields: Vec<&'a str>, values: Vec<K>, flag: bool) -> Self
+    where
+        K: SqlTuple,
+        Self: Sized,
+    {
+        if flag {
+            self = self.in_tuple_field(fields, values);
+        }
+        self
+    }
+
+    fn in_tuple_field_opt<K>(mut self, fields: Vec<&'a str>, values: Option<Vec<K>>) -> Self
+    where
+        K: SqlTuple,
+        Self: Sized,
+    {
+        if let Some(values) = values {
+            self = self.in_tuple_field(fields, values);
+        }
+        self
+    }
+
+    fn in_tuple_field_opt_flag<K>(
+        mut self,
+        fields: Vec<&'a str>,
+        values: Option<Vec<K>>,
+        flag: bool,
+    ) -> Self
+    where
+        K: SqlTuple,
+        Self: Sized,
+    {
+        if flag {
+            self = self.in_tuple_field_opt(fields, values);
+        }
+        self
+    }
+
+    /// tuple NOT IN, e.g. `.not_in_tuple((UserRole::user_id_field, UserRole::role_id_field), vec![(1, 2)])`.
+    fn not_in_tuple<F, K>(self, fields: F, values: Vec<K>) -> Self
+    where
+        F: IntoFields<'a, Self::Entity>,
+        K: SqlTuple,
+        Self: Sized,
+    {
+        self.not_in_tuple_field(fields.into_fields(), values)
+    }
+
+    fn not_in_tuple_flag<F, K>(mut self, fields: F, values: Vec<K>, flag: bool) -> Self
+    where
+        F: IntoFields<'a, Self::Entity>,
+        K: SqlTuple,
+        Self: Sized,
+    {
+        if flag {
+            self = self.not_in_tuple(fields, values);
+        }
+        self
+    }
+
+    fn not_in_tuple_opt<F, K>(mut self, fields: F, values: Option<Vec<K>>) -> Self
+    where
+        F: IntoFields<'a, Self::Entity>,
+        K: SqlTuple,
+        Self: Sized,
+    {
+        if let Some(values) = values {
+            self = self.not_in_tuple(fields, values);
+        }
+        self
+    }
+
+    fn not_in_tuple_opt_flag<F, K>(mut self, fields: F, values: Option<Vec<K>>, flag: bool) -> Self
+    where
+        F: IntoFields<'a, Self::Entity>,
+        K: SqlTuple,
+        Self: Sized,
+    {
+        if flag {
+            self = self.not_in_tuple_opt(fields, values);
+        }
+        self
+    }
+
+    fn not_in_tuple_field<K>(mut self, fields: Vec<&'a str>, values: Vec<K>) -> Self
+    where
+        K: SqlTuple,
+        Self: Sized,
+    {
+        self.wheres_push(Where::tuple(
+            fields,
+            Relationship::NotIn,
+            values
+                .into_iter()
+                .map(|v| v.into_values())
+                .collect::<Vec<_>>(),
+        ));
+        self
+    }
+
+    fn not_in_tuple_field_flag<K>(
+        mut self,
+        fields: Vec<&'a str>,
+        values: Vec<K>,
+        flag: bool,
+    ) -> Self
+    where
+        K: SqlTuple,
+        Self: Sized,
+    {
+        if flag {
+            self = self.not_in_tuple_field(fields, values);
+        }
+        self
+    }
+
+    fn not_in_tuple_field_opt<K>(mut self, fields: Vec<&'a str>, values: Option<Vec<K>>) -> Self
+    where
+        K: SqlTuple,
+        Self: Sized,
+    {
+        if let Some(values) = values {
+            self = self.not_in_tuple_field(fields, values);
+        }
+        self
+    }
+
+    fn not_in_tuple_field_opt_flag<K>(
+        mut self,
+        fields: Vec<&'a str>,
+        values: Option<Vec<K>>,
+        flag: bool,
+    ) -> Self
+    where
+        K: SqlTuple,
+        Self: Sized,
+    {
+        if flag {
+            self = self.not_in_tuple_field_opt(fields, values);
+        }
+        self
+    }
+
+    fn null<F>(self, field_func: F) -> Self
+    where
+        F: IntoField<'a, Self::Entity>,
+        Self: Sized,
+    {
+        self.null_field(field_func.into_field())
+    }
+
+    fn null_flag<F>(mut self, field_func: F, flag: bool) -> Self
+    where
+        F: IntoField<'a, Self::Entity>,
+        Self: Sized,
+    {
+        if flag {
+            self = self.null(field_func);
+        }
+        self
+    }
+
+    fn null_field(mut self, field: &'a str) -> Self
+    where
+        Self: Sized,
+    {
+        self.wheres_push(Where::new(field, Relationship::IsNull, vec![]));
+        self
+    }
+
+    fn null_field_flag(mut self, field: &'a str, flag: bool) -> Self
+    where
+        Self: Sized,
+    {
+        if flag {
+            self = self.null_field(field);
+        }
+        self
+    }
+
+    fn not_null<F>(self, field_func: F) -> Self
+    where
+        F: IntoField<'a, Self::Entity>,
+        Self: Sized,
+    {
+        self.not_null_field(field_func.into_field())
+    }
+
+    fn not_null_flag<F>(mut self, field_func: F, flag: bool) -> Self
+    where
+        F: IntoField<'a, Self::Entity>,
+        Self: Sized,
+    {
+        if flag {
+            self = self.not_null(field_func);
+        }
+        self
+    }
+
+    fn not_null_field(mut self, field: &'a str) -> Self
+    where
+        Self: Sized,
+    {
+        self.wheres_push(Where::new(field, Relationship::IsNotNull, vec![]));
+        self
+    }
+
+    fn not_null_field_flag(mut self, field: &'a str, flag: bool) -> Self
+    where
+        Self: Sized,
+    {
+        if flag {
+            self = self.not_null_field(field);
+        }
+        self
+    }
+
+    /// custom where fragment, use `?` as placeholders.
+    ///
+    /// `apply("DATE(create_time) = ?", vec!["2025-01-01"])`
+    fn apply<V>(mut self, apply_sql: &'a str, values: Vec<V>) -> Self
+    where
+        V: Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        self.wheres_push(Where::new(
+            apply_sql,
+            Relationship::Apply,
+            values.into_iter().map(|v| v.into()).collect::<Vec<_>>(),
+        ));
+        self
+    }
+
+    fn apply_flag<V>(mut self, apply_sql: &'a str, values: Vec<V>, flag: bool) -> Self
+    where
+        V: Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        if flag {
+            self = self.apply(apply_sql, values);
+        }
+        self
+    }
+
+    fn apply_opt<V>(mut self, apply_sql: &'a str, values: Option<Vec<V>>) -> Self
+    where
+        V: Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        if let Some(values) = values {
+            self = self.apply(apply_sql, values);
+        }
+        self
+    }
+
+    fn apply_opt_flag<V>(mut self, apply_sql: &'a str, values: Option<Vec<V>>, flag: bool) -> Self
+    where
+        V: Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        if flag {
+            self = self.apply_opt(apply_sql, values);
+        }
+        self
+    }
+
+    /// expression condition, columns, functions and values on both sides.
+    ///
+    /// `expr(Expr::col(User::update_time_field).gt(Expr::col(User::create_time_field)))`
+    fn expr(mut self, condition: Expr) -> Self
+    where
+        Self: Sized,
+    {
+        let (sql, values) = condition.into_parts();
+        self.wheres_push(Where::new(sql, Relationship::Expr, values));
+        self
+    }
+
+    fn expr_flag(mut self, condition: Expr, flag: bool) -> Self
+    where
+        Self: Sized,
+    {
+        if flag {
+            self = self.expr(condition);
+        }
+        self
+    }
+
+    fn expr_opt(mut self, condition: Option<Expr>) -> Self
+    where
+        Self: Sized,
+    {
+        if let Some(condition) = condition {
+            self = self.expr(condition);
+        }
+        self
+    }
+
+    fn expr_opt_flag(mut self, condition: Option<Expr>, flag: bool) -> Self
+    where
+        Self: Sized,
+    {
+        if flag {
+            self = self.expr_opt(condition);
+        }
+        self
+    }
+
+    /// `field = other_field`
+    fn eq_column<F, O>(self, field_func: F, other_field_func: O) -> Self
+    where
+        F: IntoField<'a, Self::Entity>,
+        O: IntoField<'a, Self::Entity>,
+        Self: Sized,
+    {
+        self.eq_column_field(field_func.into_field(), other_field_func.into_field())
+    }
+
+    fn eq_column_flag<F, O>(mut self, field_func: F, other_field_func: O, flag: bool) -> Self
+    where
+        F: IntoField<'a, Self::Entity>,
+        O: IntoField<'a, Self::Entity>,
+        Self: Sized,
+    {
+        if flag {
+            self = self.eq_column(field_func, other_field_func);
+        }
+        self
+    }
+
+    fn eq_column_field(self, field: &'a str, other_field: &'a str) -> Self
+    where
+        Self: Sized,
+    {
+        self.expr(Expr::raw(field).eq(Expr::raw(other_field)))
+    }
+
+    fn eq_column_field_flag(mut self, field: &'a str, other_field: &'a str, flag: bool) -> Self
+    where
+        Self: Sized,
+    {
+        if flag {
+            self = self.eq_column_field(field, other_field);
+        }
+        self
+    }
+
+    /// `field > other_field`
+    fn gt_column<F, O>(self, field_func: F, other_field_func: O) -> Self
+    where
+        F: IntoField<'a, Self::Entity>,
+        O: IntoField<'a, Self::Entity>,
+        Self: Sized,
+    {
+        self.gt_column_field(field_func.into_field(), other_field_func.into_field())
+    }
+
+    fn gt_column_flag<F, O>(mut self, field_func: F, other_field_func: O, flag: bool) -> Self
+    where
+        F: IntoField<'a, Self::Entity>,
+        O: IntoField<'a, Self::Entity>,
+        Self: Sized,
+    {
+        if flag {
+            self = self.gt_column(field_func, other_field_func);
+        }
+        self
+    }
+
+    fn gt_column_field(self, field: &'a str, other_field: &'a str) -> Self
+    where
+        Self: Sized,
+    {
+        self.expr(Expr::raw(field).gt(Expr::raw(other_field)))
+    }
+
+    fn gt_column_field_flag(mut self, field: &'a str, other_field: &'a str, flag: bool) -> Self
+    where
+        Self: Sized,
+    {
+        if flag {
+            self = self.gt_column_field(field, other_field);
+        }
+        self
+    }
+
+    /// `field->>path = ?`, compares the unquoted value at `path`, e.g. `$.theme`.
+    fn json_eq<F, V>(self, field_func: F, path: &str, value: V) -> Self
+    where
+        F: IntoField<'a, Self::Entity>,
+        V: Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        self.json_eq_field(field_func.into_field(), path, value)
+    }
+
+    fn json_eq_flag<F, V>(mut self, field_func: F, path: &str, value: V, flag: bool) -> Self
+    where
+        F: IntoField<'a, Self::Entity>,
+        V: Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        if flag {
+            self = self.json_eq(field_func, path, value);
+        }
+        self
+    }
+
+    fn json_eq_opt<F, V>(mut self, field_func: F, path: &str, value: Option<V>) -> Self
+    where
+        F: IntoField<'a, Self::Entity>,
+        V: Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        if let Some(value) = value {
+            self = self.json_eq_field(field_func.into_field(), path, value);
+        }
+        self
+    }
+
+    fn json_eq_opt_flag<F, V>(
+        mut self,
+        field_func: F,
+        path: &str,
+        value: Option<V>,
+        flag: bool,
+    ) -> Self
+    where
+        F: IntoField<'a, Self::Entity>,
+        V: Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        if flag {
+            self = self.json_eq_opt(field_func, path, value);
+        }
+        self
+    }
+
+    fn json_eq_field<V>(self, field: &'a str, path: &str, value: V) -> Self
+    where
+        V: Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        self.expr(Expr::raw(field).json_value(path).eq(Expr::value(value)))
+    }
+
+    fn json_eq_field_flag<V>(mut self, field: &'a str, path: &str, value: V, flag: bool) -> Self
+    where
+        V: Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        if flag {
+            self = self.json_eq_field(field, path, value);
+        }
+        self
+    }
+
+    fn json_eq_field_opt<V>(mut self, field: &'a str, path: &str, value: Option<V>) -> Self
+    where
+        V: Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        if let Some(value) = value {
+            self = self.json_eq_field(field, path, value);
+        }
+        self
+    }
+
+    fn json_eq_field_opt_flag<V>(
+        mut self,
+        field: &'a str,
+        path: &str,
+        value: Option<V>,
+        flag: bool,
+    ) -> Self
+    where
+        V: Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        if flag {
+            self = self.json_eq_field_opt(field, path, value);
+        }
+        self
+    }
+
+    /// `JSON_CONTAINS(field, ?)`, `value` is a JSON document or its text.
+    fn json_contains<F, V>(self, field_func: F, value: V) -> Self
+    where
+        F: IntoField<'a, Self::Entity>,
+        V: Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        self.json_contains_field(field_func.into_field(), value)
+    }
+
+    fn json_contains_flag<F, V>(mut self, field_func: F, value: V, flag: bool) -> Self
+    where
+        F: IntoField<'a, Self::Entity>,
+        V: Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        if flag {
+            self = self.json_contains(field_func, value);
+        }
+        self
+    }
+
+    fn json_contains_opt<F, V>(mut self, field_func: F, value: Option<V>) -> Self
+    where
+        F: IntoField<'a, Self::Entity>,
+        V: Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        if let Some(value) = value {
+            self = self.json_contains_field(field_func.into_field(), value);
+        }
+        self
+    }
+
+    fn json_contains_opt_flag<F, V>(mut self, field_func: F, value: Option<V>, flag: bool) -> Self
+    where
+        F: IntoField<'a, Self::Entity>,
+        V: Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        if flag {
+            self = self.json_contains_opt(field_func, value);
+        }
+        self
+    }
+
+    fn json_contains_field<V>(self, field: &'a str, value: V) -> Self
+    where
+        V: Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        self.expr(Expr::raw(field).json_contains(Expr::json(value)))
+    }
+
+    fn json_contains_field_flag<V>(mut self, field: &'a str, value: V, flag: bool) -> Self
+    where
+        V: Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        if flag {
+            self = self.json_contains_field(field, value);
+        }
+        self
+    }
+
+    fn json_contains_field_opt<V>(mut self, field: &'a str, value: Option<V>) -> Self
+    where
+        V: Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        if let Some(value) = value {
+            self = self.json_contains_field(field, value);
+        }
+        self
+    }
+
+    fn json_contains_field_opt_flag<V>(
+        mut self,
+        field: &'a str,
+        value: Option<V>,
+        flag: bool,
+    ) -> Self
+    where
+        V: Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        if flag {
+            self = self.json_contains_field_opt(field, value);
+        }
+        self
+    }
+
+    /// `JSON_OVERLAPS(field, ?)`, true when the documents share an array element or key-value pair.
+    fn json_overlaps<F, V>(self, field_func: F, value: V) -> Self
+    where
+        F: IntoField<'a, Self::Entity>,
+        V: Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        self.json_overlaps_field(field_func.into_field(), value)
+    }
+
+    fn json_overlaps_flag<F, V>(mut self, field_func: F, value: V, flag: bool) -> Self
+    where
+        F: IntoField<'a, Self::Entity>,
+        V: Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        if flag {
+            self = self.json_overlaps(field_func, value);
+        }
+        self
+    }
+
+    fn json_overlaps_opt<F, V>(mut self, field_func: F, value: Option<V>) -> Self
+    where
+        F: IntoField<'a, Self::Entity>,
+        V: Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        if let Some(value) = value {
+            self = self.json_overlaps_field(field_func.into_field(), value);
+        }
+        self
+    }
+
+    fn json_overlaps_opt_flag<F, V>(mut self, field_func: F, value: Option<V>, flag: bool) -> Self
+    where
+        F: IntoField<'a, Self::Entity>,
+        V: Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        if flag {
+            self = self.json_overlaps_opt(field_func, value);
+        }
+        self
+    }
+
+    fn json_overlaps_field<V>(self, field: &'a str, value: V) -> Self
+    where
+        V: Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        self.expr(Expr::raw(field).json_overlaps(Expr::json(value)))
+    }
+
+    fn json_overlaps_field_flag<V>(mut self, field: &'a str, value: V, flag: bool) -> Self
+    where
+        V: Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        if flag {
+            self = self.json_overlaps_field(field, value);
+        }
+        self
+    }
+
+    fn json_overlaps_field_opt<V>(mut self, field: &'a str, value: Option<V>) -> Self
+    where
+        V: Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        if let Some(value) = value {
+            self = self.json_overlaps_field(field, value);
+        }
+        self
+    }
+
+    fn json_overlaps_field_opt_flag<V>(
+        mut self,
+        field: &'a str,
+        value: Option<V>,
+        flag: bool,
+    ) -> Self
+    where
+        V: Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        if flag {
+            self = self.json_overlaps_field_opt(field, value);
+        }
+        self
+    }
+
+    /// `? MEMBER OF(field)`, true when `value` is an element of the JSON array.
+    fn member_of<F, V>(self, field_func: F, value: V) -> Self
+    where
+        F: IntoField<'a, Self::Entity>,
+        V: Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        self.member_of_field(field_func.into_field(), value)
+    }
+
+    fn member_of_flag<F, V>(mut self, field_func: F, value: V, flag: bool) -> Self
+    where
+        F: IntoField<'a, Self::Entity>,
+        V: Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        if flag {
+            self = self.member_of(field_func, value);
+        }
+        self
+    }
+
+    fn member_of_opt<F, V>(mut self, field_func: F, value: Option<V>) -> Self
+    where
+        F: IntoField<'a, Self::Entity>,
+        V: Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        if let Some(value) = value {
+            self = self.member_of_field(field_func.into_field(), value);
+        }
+        self
+    }
+
+    fn member_of_opt_flag<F, V>(mut self, field_func: F, value: Option<V>, flag: bool) -> Self
+    where
+        F: IntoField<'a, Self::Entity>,
         V: Into<SqlValue> + Clone,
         Self: Sized,
     {
-        self.wheres_push(Where::new(field, Relationship::IsNull, vec![]));
+        if flag {
+            self = self.member_of_opt(field_func, value);
+        }
         self
     }
 
-    fn null_field_flag<V>(mut self, field: &'a str, flag: bool) -> Self
+    fn member_of_field<V>(self, field: &'a str, value: V) -> Self
+    where
+        V: Into<SqlValue> + Clone,
+        Self: Sized,
+    {
+        self.expr(Expr::json(value).member_of(Expr::raw(field)))
+    }
+
+    fn member_of_field_flag<V>(mut self, field: &'a str, value: V, flag: bool) -> Self
     where
         V: Into<SqlValue> + Clone,
         Self: Sized,
     {
         if flag {
-            self = self.null_field::<V>(field);
+            self = self.member_of_field(field, value);
         }
         self
     }
 
-    fn not_null<F, V>(self, field_func: F) -> Self
+    fn member_of_field_opt<V>(mut self, field: &'a str, value: Option<V>) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
         V: Into<SqlValue> + Clone,
         Self: Sized,
     {
-        self.not_null_field::<V>(*field_func())
+        if let Some(value) = value {
+            self = self.member_of_field(field, value);
+        }
+        self
     }
 
-    fn not_null_flag<F, V>(mut self, field_func: F, flag: bool) -> Self
+    fn member_of_field_opt_flag<V>(mut self, field: &'a str, value: Option<V>, flag: bool) -> Self
     where
-        F: FnOnce() -> LambdaField<'a>,
         V: Into<SqlValue> + Clone,
         Self: Sized,
     {
         if flag {
-            self = self.not_null::<F, V>(field_func);
+            self = self.member_of_field_opt(field, value);
         }
         self
     }
 
-    fn not_null_field<V>(mut self, field: &'a str) -> Self
+    /// full-text search, `MATCH (fields) AGAINST (query)` on the columns of a FULLTEXT index.
+    fn match_against<F>(self, fields: F, query: &str, mode: MatchMode) -> Self
     where
-        V: Into<SqlValue> + Clone,
+        F: IntoFields<'a, Self::Entity>,
         Self: Sized,
     {
-        self.wheres_push(Where::new(field, Relationship::IsNotNull, vec![]));
+        self.match_against_field(fields.into_fields(), query, mode)
+    }
+
+    fn match_against_flag<F>(mut self, fields: F, query: &str, mode: MatchMode, flag: bool) -> Self
+    where
+        F: IntoFields<'a, Self::Entity>,
+        Self: Sized,
+    {
+        if flag {
+            self = self.match_against(fields, query, mode);
+        }
         self
     }
 
-    fn not_null_field_flag<V>(mut self, field: &'a str, flag: bool) -> Self
+    fn match_against_opt<F>(mut self, fields: F, query: Option<&str>, mode: MatchMode) -> Self
+    where
+        F: IntoFields<'a, Self::Entity>,
+        Self: Sized,
+    {
+        if let Some(query) = query {
+            self = self.match_against(fields, query, mode);
+        }
+        self
+    }
+
+    fn match_against_opt_flag<F>(
+        mut self,
+        fields: F,
+        query: Option<&str>,
+        mode: MatchMode,
+        flag: bool,
+    ) -> Self
+    where
+        F: IntoFields<'a, Self::Entity>,
+        Self: Sized,
+    {
+        if flag {
+            self = self.match_against_opt(fields, query, mode);
+        }
+        self
+    }
+
+    fn match_against_field(self, fields: Vec<&'a str>, query: &str, mode: MatchMode) -> Self
+    where
+        Self: Sized,
+    {
+        self.expr(Expr::match_against(&fields, query, mode))
+    }
+
+    fn match_against_field_flag(
+        mut self,
+        fields: Vec<&'a str>,
+        query: &str,
+        mode: MatchMode,
+        flag: bool,
+    ) -> Self
+    where
+        Self: Sized,
+    {
+        if flag {
+            self = self.match_against_field(fields, query, mode);
+        }
+        self
+    }
+
+    fn match_against_field_opt(
+        mut self,
+        fields: Vec<&'a str>,
+        query: Option<&str>,
+        mode: MatchMode,
+    ) -> Self
+    where
+        Self: Sized,
+    {
+        if let Some(query) = query {
+            self = self.match_against_field(fields, query, mode);
+        }
+        self
+    }
+
+    fn match_against_field_opt_flag(
+        mut self,
+        fields: Vec<&'a str>,
+        query: Option<&str>,
+        mode: MatchMode,
+        flag: bool,
+    ) -> Self
     where
-        V: Into<SqlValue> + Clone,
         Self: Sized,
     {
         if flag {
-            self = self.not_null_field::<V>(field);
+            self = self.match_against_field_opt(fields, query, mode);
         }
         self
     }
@@ -1714,12 +2848,31 @@ pub trait Wrapper<'a> {
 
     fn first(self, sql: &'a str) -> Self
     where
+        Self: Sized,
+    {
+        self.first_values::<SqlValue>(sql, vec![])
+    }
+
+    /// sql fragment placed before the statement, use `?` as placeholders.
+    fn first_values<V>(self, sql: &'a str, values: Vec<V>) -> Self
+    where
+        V: Into<SqlValue> + Clone,
         Self: Sized;
 
     fn last(self, sql: &'a str) -> Self
     where
+        Self: Sized,
+    {
+        self.last_values::<SqlValue>(sql, vec![])
+    }
+
+    /// sql fragment placed after the statement, use `?` as placeholders.
+    fn last_values<V>(self, sql: &'a str, values: Vec<V>) -> Self
+    where
+        V: Into<SqlValue> + Clone,
         Self: Sized;
 
+    /// the comment is escaped, `*/` and line breaks can't break out of it.
     fn comment(self, comment: &'a str) -> Self
     where
         Self: Sized;
@@ -1734,7 +2887,7 @@ pub trait Wrapper<'a> {
         let r#where = &wheres[0];
         where_sql += &format!(" WHERE {}", r#where.to_bind_sql());
         for (i, r#where) in wheres.iter().enumerate().skip(1) {
-            for _ in 0..bracket.dec_all_right(i) {
+            for _ in 0..bracket.right_count(i) {
                 where_sql += ")";
             }
             where_sql += if self.or_index().contains(&i) {
@@ -1742,7 +2895,7 @@ pub trait Wrapper<'a> {
             } else {
                 " AND "
             };
-            for _ in 0..bracket.dec_all_left(i) {
+            for _ in 0..bracket.left_count(i) {
                 where_sql += "(";
             }
             where_sql += r#where.to_bind_sql().as_str();
@@ -1756,93 +2909,228 @@ pub trait Wrapper<'a> {
         }
         where_sql
     }
-
-    fn bind_query<'q>(
-        &self,
-        mut query: Query<'q, MySql, MySqlArguments>,
-    ) -> Query<'q, MySql, MySqlArguments>
-    where
-        Self: Sized,
-    {
-        for r#where in self.wheres() {
-            query = bind_query(query, &r#where.values);
-        }
-        query
-    }
-
-    fn bind_query_as<'q, E: Entity>(
-        &self,
-        mut query_as: QueryAs<'q, MySql, E, MySqlArguments>,
-    ) -> QueryAs<'q, MySql, E, MySqlArguments>
-    where
-        Self: Sized,
-    {
-        for r#where in self.wheres() {
-            query_as = bind_query_as(query_as, &r#where.values);
-        }
-        query_as
-    }
-
-    fn bind_query_scalar<'q, T>(
-        &self,
-        mut query_scalar: QueryScalar<'q, MySql, T, MySqlArguments>,
-    ) -> QueryScalar<'q, MySql, T, MySqlArguments>
-    where
-        Self: Sized,
-    {
-        for r#where in self.wheres() {
-            query_scalar = bind_query_scalar(query_scalar, &r#where.values);
-        }
-        query_scalar
-    }
 }
 
 #[derive(Debug)]
 pub struct Where<'a> {
-    field: &'a str,
+    field: Cow<'a, str>,
     relationship: Relationship,
     values: Vec<SqlValue>,
+    /// values per `IN` item, more than 1 for tuple-IN.
+    width: usize,
+    /// why the condition can't be run, reported when the statement runs.
+    invalid: Option<String>,
 }
 
 impl<'a> Where<'a> {
-    fn new(field: &'a str, relationship: Relationship, values: Vec<SqlValue>) -> Self {
+    fn new(
+        field: impl Into<Cow<'a, str>>,
+        relationship: Relationship,
+        values: Vec<SqlValue>,
+    ) -> Self {
+        let is_null = matches!(values.as_slice(), [SqlValue::Null]);
+        let (relationship, values) = match relationship {
+            Relationship::Eq if is_null => (Relationship::IsNull, vec![]),
+            Relationship::Ne if is_null => (Relationship::IsNotNull, vec![]),
+            relationship => (relationship, values),
+        };
+        let values = match relationship {
+            Relationship::Like | Relationship::NotLike => like_values(values, true, true),
+            Relationship::LikeLeft => like_values(values, true, false),
+            Relationship::LikeRight => like_values(values, false, true),
+            _ => values,
+        };
         Self {
-            field,
+            field: field.into(),
             relationship,
             values,
+            width: 1,
+            invalid: None,
+        }
+    }
+
+    /// `(a, b) IN ((?, ?), (?, ?))`, every tuple must have one value per field.
+    fn tuple(fields: Vec<&'a str>, relationship: Relationship, tuples: Vec<Vec<SqlValue>>) -> Self {
+        let width = fields.len();
+        let invalid = if width == 0 {
+            Some("tuple IN needs at least one field".to_string())
+        } else {
+            tuples
+                .iter()
+                .find(|tuple| tuple.len() != width)
+                .map(|tuple| {
+                    format!(
+                        "tuple IN of ({}) needs {width} values per tuple, got {}",
+                        fields.join(", "),
+                        tuple.len()
+                    )
+                })
+        };
+        let field = if width == 1 {
+            Cow::Borrowed(fields[0])
+        } else {
+            Cow::Owned(format!("({})", fields.join(", ")))
+        };
+        Self {
+            field,
+            relationship,
+            values: tuples.into_iter().flatten().collect(),
+            width,
+            invalid,
+        }
+    }
+
+    pub(crate) fn values(&self) -> &Vec<SqlValue> {
+        &self.values
+    }
+
+    fn to_in_sql(&self) -> String {
+        let not_in = self.relationship == Relationship::NotIn;
+        if self.values.is_empty() {
+            return if not_in { "1 = 1" } else { "1 = 0" }.to_string();
+        }
+        // 0 only for an invalid tuple IN, rendered anyway
+        let width = self.width.max(1);
+        let item = if width == 1 {
+            "?".to_string()
+        } else {
+            format!("({})", vec!["?"; width].join(", "))
+        };
+        let placeholders = vec![item.as_str(); self.values.len() / width].join(", ");
+        format!(
+            "{} {} ({placeholders})",
+            self.field,
+            self.relationship.to_str()
+        )
+    }
+
+    /// values of an `IN` over `BIND_CHUNK_SIZE` values, in chunks of whole items.
+    pub(crate) fn in_chunks(&self) -> Option<Vec<Vec<SqlValue>>> {
+        if self.relationship != Relationship::In
+            || self.invalid.is_some()
+            || self.values.len() <= BIND_CHUNK_SIZE
+        {
+            return None;
         }
+        let width = self.width.max(1);
+        let chunk_size = (BIND_CHUNK_SIZE / width).max(1) * width;
+        Some(self.values.chunks(chunk_size).map(<[_]>::to_vec).collect())
+    }
+
+    pub(crate) fn replace_values(&mut self, values: Vec<SqlValue>) -> Vec<SqlValue> {
+        std::mem::replace(&mut self.values, values)
     }
 
     fn to_bind_sql(&self) -> String {
+        match self.relationship {
+            Relationship::Apply => return format!("({})", self.field),
+            Relationship::Expr => return self.field.to_string(),
+            _ => {}
+        }
         let mut sql = format!("{} {}", self.field, self.relationship.to_str());
         match self.relationship {
             Relationship::Eq
+            | Relationship::NullSafeEq
             | Relationship::Ne
             | Relationship::Gt
             | Relationship::Ge
             | Relationship::Lt
             | Relationship::Le
-            | Relationship::Like
+            | Relationship::LikeRaw
+            | Relationship::NotLikeRaw => sql += " ?",
+            Relationship::Like
             | Relationship::NotLike
             | Relationship::LikeLeft
-            | Relationship::LikeRight => sql += " ?",
+            | Relationship::LikeRight => sql += " ? ESCAPE '!'",
             Relationship::Between | Relationship::NotBetween => sql += " ? AND ?",
-            Relationship::In | Relationship::NotIn => {
-                let placeholders = self
-                    .values
-                    .iter()
-                    .map(|_| "?")
-                    .collect::<Vec<_>>()
-                    .join(", ");
-                sql += &format!(" ({placeholders})");
-            }
-            Relationship::IsNull => sql += " IS NULL",
-            Relationship::IsNotNull => sql += "IS NOT NULL",
+            Relationship::In | Relationship::NotIn => return self.to_in_sql(),
+            Relationship::IsNull
+            | Relationship::IsNotNull
+            | Relationship::Apply
+            | Relationship::Expr => {}
         }
         sql
     }
 }
 
+/// first condition that can't be run, e.g. a tuple IN with a wrong tuple width.
+pub(crate) fn invalid_where<'w>(wheres: &'w [Where]) -> Option<&'w str> {
+    wheres.iter().find_map(|r#where| r#where.invalid.as_deref())
+}
+
+/// the `IN` over `BIND_CHUNK_SIZE` values to run in several statements and its chunks, only when every
+/// condition is ANDed so the statements together match the same rows.
+pub(crate) fn split_in(
+    wheres: &[Where],
+    or_index: &HashSet<usize>,
+    bracket: &Bracket,
+) -> Option<(usize, Vec<Vec<SqlValue>>)> {
+    if !or_index.is_empty() || !bracket.is_empty() {
+        return None;
+    }
+    wheres
+        .iter()
+        .enumerate()
+        .find_map(|(index, r#where)| Some((index, r#where.in_chunks()?)))
+}
+
+/// `InvalidArgument` of a wrapper that can't be run.
+pub(crate) fn check_invalid(invalid: Option<&str>) -> crate::Result<()> {
+    match invalid {
+        Some(invalid) => Err(sqlx::Error::InvalidArgument(invalid.to_string())),
+        None => Ok(()),
+    }
+}
+
+/// wraps the values with `%` and escapes `!`, `%` and `_` with `!`,
+/// a backslash escape would depend on `NO_BACKSLASH_ESCAPES`.
+fn like_values(values: Vec<SqlValue>, left: bool, right: bool) -> Vec<SqlValue> {
+    values
+        .into_iter()
+        .map(|value| {
+            let Some(value) = value.to_like_string() else {
+                return value;
+            };
+            let mut pattern = String::with_capacity(value.len() + 2);
+            if left {
+                pattern.push('%');
+            }
+            for c in value.chars() {
+                if matches!(c, '!' | '%' | '_') {
+                    pattern.push('!');
+                }
+                pattern.push(c);
+            }
+            if right {
+                pattern.push('%');
+            }
+            SqlValue::Str(pattern)
+        })
+        .collect()
+}
+
+pub(crate) struct Clause<'a> {
+    pub(crate) sql: &'a str,
+    pub(crate) values: Vec<SqlValue>,
+}
+
+impl<'a> Clause<'a> {
+    pub(crate) fn new<V>(sql: &'a str, values: Vec<V>) -> Self
+    where
+        V: Into<SqlValue> + Clone,
+    {
+        Self {
+            sql,
+            values: values.into_iter().map(|v| v.into()).collect(),
+        }
+    }
+}
+
+pub(crate) fn comment_sql(comment: &str) -> String {
+    let comment = comment.replace(['\r', '\n'], " ").replace("*/", "* /");
+    format!(" /* {comment} */")
+}
+
 pub struct Bracket {
     left: RefCell<HashMap<usize, usize>>,
     right: RefCell<HashMap<usize, usize>>,
@@ -1872,18 +3160,16 @@ impl Bracket {
         self.inc_right_count(index, 1);
     }
 
-    fn dec_all_left(&self, index: usize) -> usize {
-        if let Some(left) = self.left.borrow_mut().remove(&index) {
-            return left;
-        }
-        0
+    fn is_empty(&self) -> bool {
+        self.left.borrow().is_empty() && self.right.borrow().is_empty()
     }
 
-    fn dec_all_right(&self, index: usize) -> usize {
-        if let Some(right) = self.right.borrow_mut().remove(&index) {
-            return right;
-        }
-        0
+    fn left_count(&self, index: usize) -> usize {
+        self.left.borrow().get(&index).copied().unwrap_or(0)
+    }
+
+    fn right_count(&self, index: usize) -> usize {
+        self.right.borrow().get(&index).copied().unwrap_or(0)
     }
 }
 
@@ -1902,17 +3188,166 @@ impl<'a> GroupHaving<'a> {
         }
     }
 
+    pub(crate) fn is_empty(&self) -> bool {
+        self.fields.is_empty() && self.having.is_none()
+    }
+
     pub(crate) fn field_push(&mut self, field: &'a str) {
         self.fields.push(field);
     }
 
+    /// replaces the HAVING clause together with its values.
     pub(crate) fn having(&mut self, having: &'a str) {
         self.having = Some(having);
+        self.values.clear();
     }
 
     pub(crate) fn value_push(&mut self, values: SqlValue) {
         self.values.push(values);
     }
+
+    pub(crate) fn values(&self) -> &Vec<SqlValue> {
+        &self.values
+    }
+
+    /// ` GROUP BY .. HAVING ..`, empty without group fields and having.
+    pub(crate) fn to_sql(&self) -> String {
+        let mut sql = String::new();
+        if !self.fields.is_empty() {
+            sql += &format!(" GROUP BY {}", self.fields.join(", "));
+        }
+        if let Some(having) = self.having {
+            sql += &format!(" HAVING {having}");
+        }
+        sql
+    }
+}
+
+#[derive(Clone, Copy)]
+pub(crate) enum LockMode {
+    Update,
+    Share,
+    ShareMode,
+}
+
+#[derive(Clone, Copy)]
+pub(crate) enum LockWait {
+    SkipLocked,
+    Nowait,
+}
+
+/// locking read clause, rendered after ORDER BY and LIMIT.
+#[derive(Clone, Copy)]
+pub(crate) struct Lock {
+    mode: LockMode,
+    pub(crate) wait: Option<LockWait>,
+}
+
+impl Lock {
+    pub(crate) fn new(mode: LockMode, wait: Option<LockWait>) -> Self {
+        Self { mode, wait }
+    }
+
+    pub(crate) fn with_wait(self, wait: LockWait) -> Self {
+        let mode = match self.mode {
+            // LOCK IN SHARE MODE has no SKIP LOCKED / NOWAIT
+            LockMode::ShareMode => LockMode::Share,
+            mode => mode,
+        };
+        Self::new(mode, Some(wait))
+    }
+
+    pub(crate) fn to_sql(self) -> String {
+        let mut sql = match self.mode {
+            LockMode::Update => " FOR UPDATE",
+            LockMode::Share => " FOR SHARE",
+            LockMode::ShareMode => " LOCK IN SHARE MODE",
+        }
+        .to_string();
+        match self.wait {
+            Some(LockWait::SkipLocked) => sql += " SKIP LOCKED",
+            Some(LockWait::Nowait) => sql += " NOWAIT",
+            None => {}
+        }
+        sql
+    }
+}
+
+#[derive(Clone, Copy)]
+pub(crate) enum IndexHint {
+    Use,
+    Force,
+    Ignore,
+}
+
+/// optimizer hints rendered after SELECT/UPDATE/DELETE, index hints after the table name.
+#[derive(Default)]
+pub(crate) struct Hints<'a> {
+    optimizer: Vec<Cow<'a, str>>,
+    index: Vec<(IndexHint, &'a [&'a str])>,
+}
+
+impl<'a> Hints<'a> {
+    pub(crate) fn push_optimizer(&mut self, hint: impl Into<Cow<'a, str>>) {
+        self.optimizer.push(hint.into());
+    }
+
+    pub(crate) fn push_index(&mut self, hint: IndexHint, indexes: &'a [&'a str]) {
+        self.index.push((hint, indexes));
+    }
+
+    /// moves the optimizer hints out, index hints stay with their table.
+    pub(crate) fn take_optimizer(&mut self) -> Self {
+        Self {
+            optimizer: std::mem::take(&mut self.optimizer),
+            index: vec![],
+        }
+    }
+
+    /// `/*+ a b */ `
+    pub(crate) fn optimizer_sql(&self) -> String {
+        Self::comment_sql(self.optimizer.iter().map(|hint| hint.to_string()).collect())
+    }
+
+    /// ` USE INDEX (a, b)`
+    pub(crate) fn index_sql(&self) -> String {
+        self.index
+            .iter()
+            .map(|(hint, indexes)| {
+                let hint = match hint {
+                    IndexHint::Use => "USE",
+                    IndexHint::Force => "FORCE",
+                    IndexHint::Ignore => "IGNORE",
+                };
+                format!(" {hint} INDEX ({})", indexes.join(", "))
+            })
+            .collect()
+    }
+
+    /// single-table DELETE has no index hints, they are rendered as `INDEX(table a)` / `NO_INDEX(table a)`
+    /// optimizer hints instead.
+    pub(crate) fn delete_optimizer_sql(&self, table: &str) -> String {
+        let mut hints = self
+            .optimizer
+            .iter()
+            .map(|hint| hint.to_string())
+            .collect::<Vec<_>>();
+        for (hint, indexes) in &self.index {
+            let hint = match hint {
+                IndexHint::Use | IndexHint::Force => "INDEX",
+                IndexHint::Ignore => "NO_INDEX",
+            };
+            hints.push(format!("{hint}({table} {})", indexes.join(", ")));
+        }
+        Self::comment_sql(hints)
+    }
+
+    fn comment_sql(hints: Vec<String>) -> String {
+        if hints.is_empty() {
+            return String::new();
+        }
+        format!("/*+ {} */ ", hints.join(" "))
+    }
 }
 
 pub(crate) struct Order<'a> {
@@ -1930,6 +3365,8 @@ impl<'a> Order<'a> {
 enum Relationship {
     // ==
     Eq,
+    // <=>
+    NullSafeEq,
     // <>
     Ne,
     // >
@@ -1946,12 +3383,16 @@ enum Relationship {
     NotBetween,
     // LIKE '%str%'
     Like,
-    // NOT LIKE 'str'
+    // NOT LIKE '%str%'
     NotLike,
     // LIKE '%str'
     LikeLeft,
     // LIKE 'str%'
     LikeRight,
+    // LIKE 'str', pattern supplied by caller
+    LikeRaw,
+    // NOT LIKE 'str', pattern supplied by caller
+    NotLikeRaw,
     // IN
     In,
     // NOT IN
@@ -1960,12 +3401,17 @@ enum Relationship {
     IsNull,
     // IS NOT NULL
     IsNotNull,
+    // custom sql fragment
+    Apply,
+    // `Expr` condition, rendered as is
+    Expr,
 }
 
 impl Relationship {
     fn to_str(&self) -> &str {
         match self {
             Relationship::Eq => "=",
+            Relationship::NullSafeEq => "<=>",
             Relationship::Ne => "<>",
             Relationship::Gt => ">",
             Relationship::Ge => ">=",
@@ -1973,12 +3419,16 @@ impl Relationship {
             Relationship::Le => "<=",
             Relationship::Between => "BETWEEN",
             Relationship::NotBetween => "NOT BETWEEN",
-            Relationship::Like | Relationship::LikeLeft | Relationship::LikeRight => "LIKE",
-            Relationship::NotLike => "NOT LIKE",
+            Relationship::Like
+            | Relationship::LikeLeft
+            | Relationship::LikeRight
+            | Relationship::LikeRaw => "LIKE",
+            Relationship::NotLike | Relationship::NotLikeRaw => "NOT LIKE",
             Relationship::In => "IN",
             Relationship::NotIn => "NOT IN",
             Relationship::IsNull => "IS NULL",
             Relationship::IsNotNull => "IS NOT NULL",
+            Relationship::Apply | Relationship::Expr => "",
         }
     }
 }
@@ -2003,7 +3453,157 @@ pub enum SqlValue {
     Time(NaiveTime),
     Date(NaiveDate),
     DateTime(NaiveDateTime),
+    DateTimeUtc(DateTime<Utc>),
     Decimal(Decimal),
+    Bytes(Vec<u8>),
+    Uuid(Uuid),
+    Json(serde_json::Value),
+    Custom(Arc<dyn BindValue>),
+}
+
+/// value bound by a custom type.
+///
+/// implemented for every type sqlx can encode for MySQL, so a newtype only needs
+/// `#[derive(sqlx::Type)]` with `#[sqlx(transparent)]` and a `From` impl for `SqlValue`:
+///
+/// ```ignore
+/// impl From<UserId> for SqlValue {
+///     fn from(value: UserId) -> Self {
+///         SqlValue::custom(value)
+///     }
+/// }
+/// ```
+pub trait BindValue: Debug + Send + Sync {
+    fn encode(&self, buf: &mut Vec<u8>) -> Result<IsNull, BoxDynError>;
+
+    fn type_info(&self) -> MySqlTypeInfo;
+}
+
+impl<T> BindValue for T
+where
+    T: for<'q> Encode<'q, MySql> + Type<MySql> + Debug + Send + Sync,
+{
+    fn encode(&self, buf: &mut Vec<u8>) -> Result<IsNull, BoxDynError> {
+        self.encode_by_ref(buf)
+    }
+
+    fn type_info(&self) -> MySqlTypeInfo {
+        self.produces().unwrap_or_else(T::type_info)
+    }
+}
+
+impl Type<MySql> for SqlValue {
+    fn type_info() -> MySqlTypeInfo {
+        <str as Type<MySql>>::type_info()
+    }
+
+    fn compatible(_ty: &MySqlTypeInfo) -> bool {
+        true
+    }
+}
+
+impl<'q> Encode<'q, MySql> for SqlValue {
+    fn encode_by_ref(&self, buf: &mut Vec<u8>) -> Result<IsNull, BoxDynError> {
+        match self {
+            SqlValue::Null => Ok(IsNull::Yes),
+            SqlValue::ISize(value) => BindValue::encode(&(*value as i64), buf),
+            SqlValue::USize(value) => BindValue::encode(&(*value as u64), buf),
+            SqlValue::I8(value) => BindValue::encode(value, buf),
+            SqlValue::U8(value) => BindValue::encode(value, buf),
+            SqlValue::I16(value) => BindValue::encode(value, buf),
+            SqlValue::U16(value) => BindValue::encode(value, buf),
+            SqlValue::I32(value) => BindValue::encode(value, buf),
+            SqlValue::U32(value) => BindValue::encode(value, buf),
+            SqlValue::I64(value) => BindValue::encode(value, buf),
+            SqlValue::U64(value) => BindValue::encode(value, buf),
+            SqlValue::F32(value) => BindValue::encode(value, buf),
+            SqlValue::F64(value) => BindValue::encode(value, buf),
+            SqlValue::Bool(value) => BindValue::encode(value, buf),
+            SqlValue::Str(value) => BindValue::encode(value, buf),
+            SqlValue::Time(value) => BindValue::encode(value, buf),
+            SqlValue::Date(value) => BindValue::encode(value, buf),
+            SqlValue::DateTime(value) => BindValue::encode(value, buf),
+            SqlValue::DateTimeUtc(value) => BindValue::encode(value, buf),
+            SqlValue::Decimal(value) => BindValue::encode(value, buf),
+            SqlValue::Bytes(value) => BindValue::encode(value, buf),
+            SqlValue::Uuid(value) => BindValue::encode(value, buf),
+            SqlValue::Json(value) => BindValue::encode(value, buf),
+            SqlValue::Custom(value) => value.encode(buf),
+        }
+    }
+
+    fn produces(&self) -> Option<MySqlTypeInfo> {
+        let type_info = match self {
+            SqlValue::Null | SqlValue::Str(_) => <str as Type<MySql>>::type_info(),
+            SqlValue::ISize(_) | SqlValue::I64(_) => <i64 as Type<MySql>>::type_info(),
+            SqlValue::USize(_) | SqlValue::U64(_) => <u64 as Type<MySql>>::type_info(),
+            SqlValue::I8(value) => BindValue::type_info(value),
+            SqlValue::U8(value) => BindValue::type_info(value),
+            SqlValue::I16(value) => BindValue::type_info(value),
+            SqlValue::U16(value) => BindValue::type_info(value),
+            SqlValue::I32(value) => BindValue::type_info(value),
+            SqlValue::U32(value) => BindValue::type_info(value),
+            SqlValue::F32(value) => BindValue::type_info(value),
+            SqlValue::F64(value) => BindValue::type_info(value),
+            SqlValue::Bool(value) => BindValue::type_info(value),
+            SqlValue::Time(value) => BindValue::type_info(value),
+            SqlValue::Date(value) => BindValue::type_info(value),
+            SqlValue::DateTime(value) => BindValue::type_info(value),
+            SqlValue::DateTimeUtc(value) => BindValue::type_info(value),
+            SqlValue::Decimal(value) => BindValue::type_info(value),
+            SqlValue::Bytes(value) => BindValue::type_info(value),
+            SqlValue::Uuid(value) => BindValue::type_info(value),
+            SqlValue::Json(value) => BindValue::type_info(value),
+            SqlValue::Custom(value) => value.type_info(),
+        };
+        Some(type_info)
+    }
+}
+
+impl SqlValue {
+    pub fn custom<T>(value: T) -> Self
+    where
+        T: BindValue + 'static,
+    {
+        SqlValue::Custom(Arc::new(value))
+    }
+
+    pub(crate) fn is_json(&self) -> bool {
+        match self {
+            SqlValue::Json(_) => true,
+            SqlValue::Custom(value) => value.type_info().name() == "JSON",
+            _ => false,
+        }
+    }
+
+    fn to_like_string(&self) -> Option<String> {
+        let value = match self {
+            SqlValue::Null | SqlValue::Bytes(_) | SqlValue::Json(_) | SqlValue::Custom(_) => {
+                return None;
+            }
+            SqlValue::ISize(value) => value.to_string(),
+            SqlValue::USize(value) => value.to_string(),
+            SqlValue::I8(value) => value.to_string(),
+            SqlValue::U8(value) => value.to_string(),
+            SqlValue::I16(value) => value.to_string(),
+            SqlValue::U16(value) => value.to_string(),
+            SqlValue::I32(value) => value.to_string(),
+            SqlValue::U32(value) => value.to_string(),
+            SqlValue::I64(value) => value.to_string(),
+            SqlValue::U64(value) => value.to_string(),
+            SqlValue::F32(value) => value.to_string(),
+            SqlValue::F64(value) => value.to_string(),
+            SqlValue::Bool(value) => value.to_string(),
+            SqlValue::Str(value) => value.clone(),
+            SqlValue::Time(value) => value.to_string(),
+            SqlValue::Date(value) => value.to_string(),
+            SqlValue::DateTime(value) => value.to_string(),
+            SqlValue::DateTimeUtc(value) => value.to_string(),
+            SqlValue::Decimal(value) => value.to_string(),
+            SqlValue::Uuid(value) => value.to_string(),
+        };
+        Some(value)
+    }
 }
 
 impl From<isize> for SqlValue {
@@ -2119,3 +3719,179 @@ impl From<Decimal> for SqlValue {
         SqlValue::Decimal(value)
     }
 }
+
+impl From<DateTime<Utc>> for SqlValue {
+    fn from(value: DateTime<Utc>) -> Self {
+        SqlValue::DateTimeUtc(value)
+    }
+}
+
+impl From<Vec<u8>> for SqlValue {
+    fn from(value: Vec<u8>) -> Self {
+        SqlValue::Bytes(value)
+    }
+}
+
+impl From<&[u8]> for SqlValue {
+    fn from(value: &[u8]) -> Self {
+        SqlValue::Bytes(value.to_vec())
+    }
+}
+
+impl From<Uuid> for SqlValue {
+    fn from(value: Uuid) -> Self {
+        SqlValue::Uuid(value)
+    }
+}
+
+impl From<serde_json::Value> for SqlValue {
+    fn from(value: serde_json::Value) -> Self {
+        SqlValue::Json(value)
+    }
+}
+
+/// typed JSON document, e.g. `Json(settings)` for a `Json<Settings>` field.
+impl<T> From<Json<T>> for SqlValue
+where
+    T: Serialize + Debug + Send + Sync + 'static,
+{
+    fn from(value: Json<T>) -> Self {
+        SqlValue::custom(value)
+    }
+}
+
+impl<T> From<Option<T>> for SqlValue
+where
+    T: Into<SqlValue>,
+{
+    fn from(value: Option<T>) -> Self {
+        value.map_or(SqlValue::Null, Into::into)
+    }
+}
+
+/// values of a multi-column key, a single value or a tuple such as `(user_id, role_id)`.
+pub trait SqlTuple {
+    fn into_values(self) -> Vec<SqlValue>;
+}
+
+impl<V> SqlTuple for V
+where
+    V: Into<SqlValue>,
+{
+    fn into_values(self) -> Vec<SqlValue> {
+        vec![self.into()]
+    }
+}
+
+macro_rules! impl_sql_tuple {
+    ($($name:ident),+) => {
+        impl<$($name),+> SqlTuple for ($($name,)+)
+        where
+            $($name: Into<SqlValue>,)+
+        {
+            #[allow(non_snake_case)]
+            fn into_values(self) -> Vec<SqlValue> {
+                let ($($name,)+) = self;
+                vec![$($name.into()),+]
+            }
+        }
+    };
+}
+
+impl_sql_tuple!(A, B);
+impl_sql_tuple!(A, B, C);
+impl_sql_tuple!(A, B, C, D);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(r#where: &Where) -> String {
+        format!("{:?}", r#where.values())
+    }
+
+    #[test]
+    fn like_escapes_with_exclamation_mark() {
+        let r#where = Where::new("name", Relationship::Like, vec!["50%_a!b\\c".into()]);
+        assert_eq!(r#where.to_bind_sql(), "name LIKE ? ESCAPE '!'");
+        assert_eq!(values(&r#where), r#"[Str("%50!%!_a!!b\\c%")]"#);
+    }
+
+    #[test]
+    fn like_left_right_and_raw() {
+        let left = Where::new("name", Relationship::LikeLeft, vec!["bob".into()]);
+        assert_eq!(values(&left), r#"[Str("%bob")]"#);
+        let right = Where::new("name", Relationship::LikeRight, vec!["bob".into()]);
+        assert_eq!(values(&right), r#"[Str("bob%")]"#);
+        let not_like = Where::new("name", Relationship::NotLike, vec!["b_b".into()]);
+        assert_eq!(not_like.to_bind_sql(), "name NOT LIKE ? ESCAPE '!'");
+        assert_eq!(values(&not_like), r#"[Str("%b!_b%")]"#);
+        let raw = Where::new("name", Relationship::LikeRaw, vec!["b_b%".into()]);
+        assert_eq!(raw.to_bind_sql(), "name LIKE ?");
+        assert_eq!(values(&raw), r#"[Str("b_b%")]"#);
+    }
+
+    #[test]
+    fn empty_in_lists() {
+        let r#in = Where::new("ID", Relationship::In, vec![]);
+        assert_eq!(r#in.to_bind_sql(), "1 = 0");
+        let not_in = Where::new("ID", Relationship::NotIn, vec![]);
+        assert_eq!(not_in.to_bind_sql(), "1 = 1");
+    }
+
+    #[test]
+    fn large_in_lists_are_one_list() {
+        let ids = (0..BIND_CHUNK_SIZE as u64 + 1)
+            .map(SqlValue::from)
+            .collect::<Vec<_>>();
+        let placeholders = vec!["?"; BIND_CHUNK_SIZE + 1].join(", ");
+        let r#in = Where::new("ID", Relationship::In, ids.clone());
+        assert_eq!(r#in.to_bind_sql(), format!("ID IN ({placeholders})"));
+        let chunks = r#in.in_chunks().unwrap();
+        assert_eq!(
+            chunks.iter().map(Vec::len).collect::<Vec<_>>(),
+            [BIND_CHUNK_SIZE, 1]
+        );
+        let not_in = Where::new("ID", Relationship::NotIn, ids);
+        assert_eq!(not_in.to_bind_sql(), format!("ID NOT IN ({placeholders})"));
+        assert!(not_in.in_chunks().is_none());
+    }
+
+    #[test]
+    fn tuple_in_chunks_keep_whole_tuples() {
+        let values = (0..BIND_CHUNK_SIZE as u64 + 2)
+            .map(SqlValue::from)
+            .collect::<Vec<_>>();
+        let mut r#in = Where::new("(a, b, c)", Relationship::In, values);
+        r#in.width = 3;
+        let chunks = r#in.in_chunks().unwrap();
+        assert!(chunks.iter().all(|chunk| chunk.len() % 3 == 0));
+        assert_eq!(chunks[0].len(), BIND_CHUNK_SIZE / 3 * 3);
+    }
+
+    #[test]
+    fn option_values_map_to_null() {
+        assert!(matches!(SqlValue::from(None::<i32>), SqlValue::Null));
+        assert!(matches!(SqlValue::from(Some(1i32)), SqlValue::I32(1)));
+        let mut buf = vec![];
+        let is_null = SqlValue::Null.encode_by_ref(&mut buf).unwrap();
+        assert!(matches!(is_null, IsNull::Yes));
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn custom_values_encode_as_the_inner_type() {
+        let custom = SqlValue::custom(7u64);
+        let mut custom_buf = vec![];
+        let mut plain_buf = vec![];
+        let custom_null = custom.encode_by_ref(&mut custom_buf).unwrap();
+        let plain_null = SqlValue::U64(7).encode_by_ref(&mut plain_buf).unwrap();
+        assert!(matches!(
+            (custom_null, plain_null),
+            (IsNull::No, IsNull::No)
+        ));
+        assert_eq!(custom_buf, plain_buf);
+        assert_eq!(custom.produces(), Some(<u64 as Type<MySql>>::type_info()));
+        assert!(SqlValue::from(serde_json::json!({"a": 1})).is_json());
+    }
+}